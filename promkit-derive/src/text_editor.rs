@@ -92,7 +92,8 @@ pub fn create_state(attr: &syn::Attribute) -> Result<TokenStream, Error> {
             mask: #mask,
             edit_mode: #edit_mode,
             word_break_chars: #word_break_chars,
-            lines: Default::default()
+            lines: Default::default(),
+            cursor_shape: Default::default()
         }
     })
 }
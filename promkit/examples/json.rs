@@ -242,7 +242,10 @@ fn main() -> anyhow::Result<()> {
         .iter(),
     );
 
-    let mut p = Json::new(stream).title("JSON viewer").prompt()?;
+    let mut p = Json::new(stream)
+        .title("JSON viewer")
+        .prompt()?
+        .fullscreen();
     println!("result: {:?}", p.run()?);
     Ok(())
 }
@@ -3,7 +3,8 @@ use promkit::preset::listbox::Listbox;
 fn main() -> anyhow::Result<()> {
     let mut p = Listbox::new(0..100)
         .title("What number do you like?")
-        .prompt()?;
+        .prompt()?
+        .mouse_capture();
     println!("result: {:?}", p.run()?);
     Ok(())
 }
@@ -0,0 +1,22 @@
+use std::{sync::mpsc, thread, time::Duration};
+
+use promkit::preset::listbox::Listbox;
+
+fn main() -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for i in 0..100 {
+            thread::sleep(Duration::from_millis(100));
+            if sender.send(format!("item {}", i)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut p = Listbox::new(Vec::<String>::new())
+        .title("Items arrive in the background")
+        .listbox_lines(10)
+        .prompt()?;
+    println!("result: {:?}", p.run_with_receiver(receiver)?);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use promkit::preset::readline::Readline;
+use promkit::{preset::readline::Readline, Error};
 
 fn main() -> anyhow::Result<()> {
     let mut p = Readline::default().prompt()?;
@@ -8,10 +8,11 @@ fn main() -> anyhow::Result<()> {
             Ok(cmd) => {
                 println!("result: {:?}", cmd);
             }
-            Err(_) => {
+            Err(Error::Interrupted | Error::Eof) => {
                 println!("Bye!");
                 break;
             }
+            Err(e) => return Err(e.into()),
         }
     }
     Ok(())
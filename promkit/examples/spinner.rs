@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use promkit::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    grapheme::StyledGraphemes,
+    pane::Pane,
+    Finalizer, Prompt, PromptSignal, Renderer,
+};
+
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+struct Spinner {
+    ticks: usize,
+}
+
+impl Finalizer for Spinner {
+    type Return = usize;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(self.ticks)
+    }
+}
+
+impl Renderer for Spinner {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        let (rows, offset) = StyledGraphemes::from(format!(
+            "{} Loading... (press Enter to stop)",
+            FRAMES[self.ticks % FRAMES.len()]
+        ))
+        .matrixify(width as usize, height as usize, 0);
+        vec![Pane::new(rows, offset)]
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            }) => Ok(PromptSignal::Quit),
+            _ => Ok(PromptSignal::Continue),
+        }
+    }
+
+    fn on_tick(&mut self) -> anyhow::Result<PromptSignal> {
+        self.ticks += 1;
+        Ok(PromptSignal::Continue)
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut p = Prompt::new(Spinner { ticks: 0 }).tick_interval(Duration::from_millis(80));
+    println!("ticks: {:?}", p.run()?);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::fs;
+
+use promkit::{
+    block::{Block, BorderType},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::Color,
+    },
+    indicator::Indicator,
+    layout::{Constraint, Layout},
+    listbox::{self, Listbox},
+    pane::Pane,
+    style::StyleBuilder,
+    text::{self, Text},
+    Finalizer, PaneFactory, Prompt, PromptSignal, Renderer,
+};
+
+struct Picker {
+    files: listbox::State,
+    preview: text::State,
+}
+
+impl Picker {
+    fn update_preview(&mut self) {
+        let path = self.files.listbox.get().to_string();
+        self.preview.text = Text::from(fs::read_to_string(path).unwrap_or_default());
+    }
+}
+
+impl Finalizer for Picker {
+    type Return = String;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(self.files.listbox.get().to_string())
+    }
+}
+
+impl Renderer for Picker {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        vec![Layout::columns()
+            .child(
+                Constraint::Percentage(30),
+                Block::new(&self.files)
+                    .border(BorderType::Rounded)
+                    .title("files"),
+            )
+            .child(
+                Constraint::Fill,
+                Block::new(&self.preview)
+                    .border(BorderType::Rounded)
+                    .title("preview"),
+            )
+            .create_pane(width, height)]
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            }) => return Ok(PromptSignal::Quit),
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }) => return Err(promkit::Error::Interrupted.into()),
+            Event::Key(KeyEvent {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::NONE,
+                ..
+            }) => {
+                self.files.listbox.backward();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::NONE,
+                ..
+            }) => {
+                self.files.listbox.forward();
+            }
+            _ => return Ok(PromptSignal::Continue),
+        }
+        self.update_preview();
+        Ok(PromptSignal::Continue)
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut files = fs::read_dir(".")?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut picker = Picker {
+        files: listbox::State {
+            listbox: Listbox::from_displayable(files),
+            active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
+            indicator: Indicator {
+                scrollbar: None,
+                footer: Some(StyleBuilder::new().fgc(Color::DarkGrey).build()),
+            },
+            ..Default::default()
+        },
+        preview: text::State {
+            style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+            ..Default::default()
+        },
+    };
+    picker.update_preview();
+
+    let mut p = Prompt::new(picker).fullscreen();
+    println!("result: {:?}", p.run()?);
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Pre-seeded answers for running prompts without user interaction.
+//!
+//! A prompt with an id (see `Prompt::id`) looks up its answer before
+//! showing the UI. If an answer exists and passes validation,
+//! the prompt returns it immediately. The answer is parsed in the same way
+//! as the non-interactive input described in [`crate::fallback`],
+//! e.g. the text or index of an item for `Listbox`.
+//!
+//! Ids are matched case-insensitively, and characters other than
+//! ASCII alphanumerics are treated as `_`, so that the prompt `release-notes`
+//! can be answered by the `PROMKIT_ANSWER_RELEASE_NOTES` environment variable.
+//!
+//! ```ignore
+//! let name = Readline::default()
+//!     .prompt()?
+//!     .id("name")
+//!     .answers(Answers::from_json_file("answers.json")?)
+//!     .run()?;
+//! ```
+//!
+//! Answers can also be read from TOML files with the `toml` feature,
+//! see [`Answers::from_toml_file`].
+use std::{collections::HashMap, env, fs, path::Path};
+
+/// The prefix of environment variables holding answers.
+pub const ENV_PREFIX: &str = "PROMKIT_ANSWER_";
+
+/// A set of answers keyed by prompt id.
+#[derive(Clone, Debug, Default)]
+pub struct Answers(HashMap<String, String>);
+
+impl Answers {
+    /// Collects answers from environment variables named `PROMKIT_ANSWER_<ID>`.
+    pub fn from_env() -> Self {
+        Self(
+            env::vars()
+                .filter_map(|(key, value)| {
+                    key.strip_prefix(ENV_PREFIX)
+                        .map(|id| (Self::normalize(id), value))
+                })
+                .collect(),
+        )
+    }
+
+    /// Parses answers from a JSON object mapping prompt ids to answers.
+    ///
+    /// Strings are used as they are, and other values (e.g. numbers
+    /// or booleans) are converted to their JSON representation.
+    pub fn from_json_str(json: &str) -> anyhow::Result<Self> {
+        let map = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(json)?;
+        Ok(Self(
+            map.into_iter()
+                .map(|(id, value)| {
+                    let answer = match value {
+                        serde_json::Value::String(s) => s,
+                        v => v.to_string(),
+                    };
+                    (Self::normalize(&id), answer)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Reads answers from a JSON file, see [`Answers::from_json_str`].
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        Self::from_json_str(&fs::read_to_string(path)?)
+    }
+
+    /// Parses answers from a TOML table mapping prompt ids to answers.
+    ///
+    /// Strings are used as they are, and other values (e.g. integers
+    /// or booleans) are converted to their TOML representation.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> anyhow::Result<Self> {
+        let table = toml.parse::<toml::Table>()?;
+        Ok(Self(
+            table
+                .into_iter()
+                .map(|(id, value)| {
+                    let answer = match value {
+                        toml::Value::String(s) => s,
+                        v => v.to_string(),
+                    };
+                    (Self::normalize(&id), answer)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Reads answers from a TOML file, see [`Answers::from_toml_str`].
+    #[cfg(feature = "toml")]
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        Self::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// Adds an answer for the prompt with the given id.
+    pub fn with<I: AsRef<str>, A: Into<String>>(mut self, id: I, answer: A) -> Self {
+        self.0.insert(Self::normalize(id.as_ref()), answer.into());
+        self
+    }
+
+    /// Returns the answer for the prompt with the given id, if any.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.0.get(&Self::normalize(id)).map(String::as_str)
+    }
+
+    fn normalize(id: &str) -> String {
+        id.chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() {
+                    ch.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod get {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let answers = Answers::default().with("release-notes", "yes");
+            assert_eq!(Some("yes"), answers.get("release-notes"));
+            assert_eq!(Some("yes"), answers.get("RELEASE_NOTES"));
+            assert_eq!(None, answers.get("release"));
+        }
+    }
+
+    mod from_json_str {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let answers = Answers::from_json_str(r#"{"name": "promkit", "count": 3}"#).unwrap();
+            assert_eq!(Some("promkit"), answers.get("name"));
+            assert_eq!(Some("3"), answers.get("count"));
+        }
+
+        #[test]
+        fn test_with_invalid_json() {
+            assert!(Answers::from_json_str(r#"["promkit"]"#).is_err());
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    mod from_toml_str {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let answers = Answers::from_toml_str("name = \"promkit\"\ncount = 3\n").unwrap();
+            assert_eq!(Some("promkit"), answers.get("name"));
+            assert_eq!(Some("3"), answers.get("count"));
+        }
+
+        #[test]
+        fn test_with_invalid_toml() {
+            assert!(Answers::from_toml_str("name = ").is_err());
+        }
+    }
+}
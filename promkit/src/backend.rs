@@ -0,0 +1,94 @@
+//! The terminal that prompts read their size and events from.
+//!
+//! `Terminal` draws through its writer, but asks a `Backend` for everything
+//! it reads from the terminal: the size, the cursor position, raw mode and events.
+//! By default, this is the terminal of the process through crossterm ([`Crossterm`]).
+//! Another backend can be set with `Prompt::backend`, e.g. a virtual terminal
+//! that drives the prompt in tests, see [`crate::headless`].
+//!
+//! Note that `Prompt::run_async` always reads events from crossterm's `EventStream`.
+use std::{
+    io::{self, IsTerminal},
+    time::Duration,
+};
+
+use crate::crossterm::{
+    cursor,
+    event::{self, Event},
+    terminal,
+};
+
+/// A trait for the terminals that prompts read their size and events from.
+///
+/// It is shared with the thread that reads events while
+/// messages are received, see `Prompt::run_with_receiver`.
+pub trait Backend: Send + Sync {
+    /// Returns whether events can be read interactively, i.e. stdin is a terminal.
+    fn is_interactive(&self) -> bool;
+
+    /// Returns the size of the terminal as `(columns, rows)`.
+    fn size(&self) -> io::Result<(u16, u16)>;
+
+    /// Returns the position of the cursor as `(column, row)`,
+    /// or `None` if it cannot be queried.
+    fn cursor_position(&self) -> io::Result<Option<(u16, u16)>>;
+
+    /// Enables raw mode.
+    fn enable_raw_mode(&self) -> io::Result<()>;
+
+    /// Disables raw mode.
+    fn disable_raw_mode(&self) -> io::Result<()>;
+
+    /// Returns whether raw mode is enabled.
+    fn is_raw_mode_enabled(&self) -> io::Result<bool>;
+
+    /// Waits for an event for at most `timeout`,
+    /// and returns whether one can be read without blocking.
+    fn poll(&self, timeout: Duration) -> io::Result<bool>;
+
+    /// Reads the next event, blocking until one is available.
+    fn read(&self) -> io::Result<Event>;
+}
+
+/// The terminal of the process, used through crossterm.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Crossterm;
+
+impl Backend for Crossterm {
+    fn is_interactive(&self) -> bool {
+        io::stdin().is_terminal()
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn cursor_position(&self) -> io::Result<Option<(u16, u16)>> {
+        // Querying the cursor position writes an escape sequence to stdout,
+        // so it is only done when stdout is a terminal.
+        if !io::stdout().is_terminal() {
+            return Ok(None);
+        }
+        cursor::position().map(Some)
+    }
+
+    fn enable_raw_mode(&self) -> io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&self) -> io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+
+    fn is_raw_mode_enabled(&self) -> io::Result<bool> {
+        terminal::is_raw_mode_enabled()
+    }
+
+    fn poll(&self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&self) -> io::Result<Event> {
+        event::read()
+    }
+}
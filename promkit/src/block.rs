@@ -0,0 +1,339 @@
+//! Borders, titles and padding around panes.
+//!
+//! A `Block` wraps any `PaneFactory` and decorates its pane with an optional
+//! box border, a title embedded in the top border, and padding.
+//! The wrapped factory receives the width and height left inside the decoration.
+//!
+//! ```ignore
+//! Block::new(&self.listbox_state)
+//!     .border(BorderType::Rounded)
+//!     .border_style(StyleBuilder::new().fgc(Color::DarkGrey).build())
+//!     .title("files")
+//!     .padding(Padding::uniform(1))
+//!     .create_pane(width, height)
+//! ```
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    pane::{Caret, Pane},
+    PaneFactory,
+};
+
+/// The line style of a border.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorderType {
+    /// `┌─┐` with square corners.
+    #[default]
+    Plain,
+    /// `╭─╮` with rounded corners.
+    Rounded,
+    /// `╔═╗` with double lines.
+    Double,
+    /// `┏━┓` with thick lines.
+    Thick,
+    /// `+-+` with ASCII characters only.
+    Ascii,
+}
+
+impl BorderType {
+    /// Returns the characters of the border in the order of
+    /// top left, top right, bottom left, bottom right, horizontal and vertical.
+    fn symbols(&self) -> [char; 6] {
+        match self {
+            BorderType::Plain => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderType::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderType::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderType::Thick => ['┏', '┓', '┗', '┛', '━', '┃'],
+            BorderType::Ascii => ['+', '+', '+', '+', '-', '|'],
+        }
+    }
+}
+
+/// The space between the border (or the edge of the block) and the content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Padding {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl Padding {
+    /// Creates a padding with the same size on all sides.
+    pub fn uniform(size: u16) -> Self {
+        Self {
+            top: size,
+            right: size,
+            bottom: size,
+            left: size,
+        }
+    }
+
+    /// Creates a padding with the given sizes at the top and bottom
+    /// and at the left and right.
+    pub fn symmetric(vertical: u16, horizontal: u16) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+}
+
+/// A decoration around the pane created by another `PaneFactory`.
+pub struct Block<'a> {
+    /// The factory creating the content of the block.
+    inner: Box<dyn PaneFactory + 'a>,
+    /// The line style of the border, or `None` for no border.
+    border: Option<BorderType>,
+    /// Style applied to the border.
+    border_style: ContentStyle,
+    /// The title embedded in the top border, or shown in its own row without a border.
+    title: Option<String>,
+    /// Style applied to the title.
+    title_style: ContentStyle,
+    /// The space around the content.
+    padding: Padding,
+}
+
+impl<'a> Block<'a> {
+    /// Wraps the given factory without any decoration.
+    pub fn new<F: PaneFactory + 'a>(factory: F) -> Self {
+        Self {
+            inner: Box::new(factory),
+            border: None,
+            border_style: ContentStyle::default(),
+            title: None,
+            title_style: ContentStyle::default(),
+            padding: Padding::default(),
+        }
+    }
+
+    /// Draws a border with the given line style.
+    /// Blocks narrower than two columns are drawn without it.
+    pub fn border(mut self, border: BorderType) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Sets the style of the border.
+    pub fn border_style(mut self, style: ContentStyle) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Sets the title of the block.
+    pub fn title<T: AsRef<str>>(mut self, title: T) -> Self {
+        self.title = Some(title.as_ref().to_string());
+        self
+    }
+
+    /// Sets the style of the title.
+    pub fn title_style(mut self, style: ContentStyle) -> Self {
+        self.title_style = style;
+        self
+    }
+
+    /// Sets the padding around the content.
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the title cut off to fit within the given width.
+    fn styled_title(&self, width: usize) -> Option<StyledGraphemes> {
+        self.title.as_ref().map(|title| {
+            let mut used = 0;
+            StyledGraphemes::from_str(title, self.title_style)
+                .iter()
+                .take_while(|g| {
+                    used += g.width();
+                    used <= width
+                })
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Returns a horizontal border row with the given corners and an optional title.
+    fn horizontal_border(
+        &self,
+        width: usize,
+        left: char,
+        right: char,
+        horizontal: char,
+        title: Option<StyledGraphemes>,
+    ) -> StyledGraphemes {
+        let mut row = StyledGraphemes::default();
+        row.push_back(StyledGrapheme::new(left, self.border_style));
+        let mut used = 2;
+        if let Some(mut title) = title {
+            used += title.widths();
+            row.append(&mut title);
+        }
+        row.append(&mut StyledGraphemes::from_str(
+            horizontal.to_string().repeat(width.saturating_sub(used)),
+            self.border_style,
+        ));
+        row.push_back(StyledGrapheme::new(right, self.border_style));
+        row
+    }
+}
+
+impl PaneFactory for Block<'_> {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let (width, height) = (width as usize, height as usize);
+        // A border needs two columns, so narrower blocks are drawn without it
+        // rather than with rows wider than the pane.
+        let border = self.border.filter(|_| width >= 2);
+        let edge = if border.is_some() { 1 } else { 0 };
+        let title_row = if border.is_none() && self.title.is_some() {
+            1
+        } else {
+            0
+        };
+        let padding = self.padding;
+
+        let inner_width =
+            width.saturating_sub(2 * edge + padding.left as usize + padding.right as usize);
+        let inner_height = height
+            .saturating_sub(2 * edge + title_row + padding.top as usize + padding.bottom as usize);
+        let (content, caret) = if inner_width == 0 || inner_height == 0 {
+            (vec![], None)
+        } else {
+            let pane = self
+                .inner
+                .create_pane(inner_width as u16, inner_height as u16);
+            (pane.extract(inner_height), pane.extract_caret(inner_height))
+        };
+
+        let blank = StyledGraphemes::from(" ".repeat(padding.left as usize));
+        let mut rows = vec![];
+        let body = (0..padding.top as usize)
+            .map(|_| StyledGraphemes::default())
+            .chain(content.into_iter().map(|mut row| {
+                let mut padded = blank.clone();
+                padded.append(&mut row);
+                padded
+            }))
+            .chain((0..padding.bottom as usize).map(|_| StyledGraphemes::default()));
+
+        match border {
+            Some(border) => {
+                let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] =
+                    border.symbols();
+                rows.push(self.horizontal_border(
+                    width,
+                    top_left,
+                    top_right,
+                    horizontal,
+                    self.styled_title(width.saturating_sub(2)),
+                ));
+                for mut row in body {
+                    let mut line = StyledGraphemes::default();
+                    line.push_back(StyledGrapheme::new(vertical, self.border_style));
+                    let fill = width.saturating_sub(2 + row.widths());
+                    line.append(&mut row);
+                    line.append(&mut StyledGraphemes::from(" ".repeat(fill)));
+                    line.push_back(StyledGrapheme::new(vertical, self.border_style));
+                    rows.push(line);
+                }
+                rows.push(self.horizontal_border(
+                    width,
+                    bottom_left,
+                    bottom_right,
+                    horizontal,
+                    None,
+                ));
+            }
+            None => {
+                if let Some(title) = self.styled_title(width) {
+                    rows.push(title);
+                }
+                rows.extend(body);
+            }
+        }
+
+        let caret = caret.map(|caret| Caret {
+            row: caret.row + edge + title_row + padding.top as usize,
+            column: caret.column + edge + padding.left as usize,
+            ..caret
+        });
+        Pane::new(rows, 0).with_caret(caret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod create_pane {
+        use crate::text;
+
+        use super::super::*;
+
+        fn render(block: Block, width: u16, height: u16) -> Vec<String> {
+            block
+                .create_pane(width, height)
+                .extract(height as usize)
+                .iter()
+                .map(|row| row.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                vec!["╭files─╮", "│a     │", "│b     │", "╰──────╯"],
+                render(
+                    Block::new(text::State::with_text("a\nb"))
+                        .border(BorderType::Rounded)
+                        .title("files"),
+                    8,
+                    10
+                ),
+            );
+        }
+
+        #[test]
+        fn test_with_padding() {
+            assert_eq!(
+                vec!["+------+", "|      |", "|  a   |", "|      |", "+------+"],
+                render(
+                    Block::new(text::State::with_text("a\nb"))
+                        .border(BorderType::Ascii)
+                        .padding(Padding::symmetric(1, 2)),
+                    8,
+                    5
+                ),
+            );
+        }
+
+        #[test]
+        fn test_with_narrow_width() {
+            let block = || {
+                Block::new(text::State::with_text("a"))
+                    .border(BorderType::Plain)
+                    .title("files")
+            };
+            assert_eq!(vec!["f", "a"], render(block(), 1, 3));
+            assert_eq!(vec![""], render(block(), 0, 3));
+        }
+
+        #[test]
+        fn test_without_border() {
+            assert_eq!(
+                vec!["title", " a"],
+                render(
+                    Block::new(text::State::with_text("a"))
+                        .title("title")
+                        .padding(Padding {
+                            left: 1,
+                            ..Default::default()
+                        }),
+                    8,
+                    5
+                ),
+            );
+        }
+    }
+}
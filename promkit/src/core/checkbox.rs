@@ -124,6 +124,12 @@ impl Checkbox {
     pub fn move_to_tail(&mut self) {
         self.listbox.move_to_tail()
     }
+
+    /// Moves the cursor to the item at the given position in the listbox, if it exists.
+    /// Returns `true` if the cursor was moved, `false` otherwise.
+    pub fn move_to(&mut self, position: usize) -> bool {
+        self.listbox.move_to(position)
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,7 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, indicator::Indicator, pane::Pane,
+    PaneFactory,
+};
 
 use super::Checkbox;
 
@@ -28,10 +31,39 @@ pub struct State {
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+
+    /// Scrollbar and position footer shown with the items.
+    pub indicator: Indicator,
 }
 
-impl PaneFactory for State {
-    fn create_pane(&self, width: u16, height: u16) -> Pane {
+impl Default for State {
+    /// Creates a state with no items, `❯ ` as the cursor,
+    /// `☒` and `☐` as the marks, and no styles.
+    fn default() -> Self {
+        Self {
+            checkbox: Checkbox::from_displayable(Vec::<String>::new()),
+            cursor: String::from("❯ "),
+            active_mark: '☒',
+            inactive_mark: '☐',
+            active_item_style: Default::default(),
+            inactive_item_style: Default::default(),
+            lines: None,
+            indicator: Default::default(),
+        }
+    }
+}
+
+impl State {
+    /// Returns the index of the item drawn at the given row of the pane
+    /// created with the given width, if any.
+    pub fn index_at(&self, width: u16, row: usize) -> Option<usize> {
+        self.rows(width, row + 1).get(row).map(|(index, _)| *index)
+    }
+
+    /// Returns the rows of the items shown within the given size,
+    /// each with the index of its item.
+    fn rows(&self, width: u16, height: usize) -> Vec<(usize, StyledGraphemes)> {
+        let inner_width = self.indicator.inner_width(width) as usize;
         let f = |idx: usize| -> StyledGraphemes {
             if self.checkbox.picked_indexes().contains(&idx) {
                 StyledGraphemes::from(format!("{} ", self.active_mark))
@@ -40,13 +72,7 @@ impl PaneFactory for State {
             }
         };
 
-        let height = match self.lines {
-            Some(lines) => lines.min(height as usize),
-            None => height as usize,
-        };
-
-        let matrix = self
-            .checkbox
+        self.checkbox
             .items()
             .iter()
             .enumerate()
@@ -54,7 +80,7 @@ impl PaneFactory for State {
                 *i >= self.checkbox.position() && *i < self.checkbox.position() + height
             })
             .map(|(i, item)| {
-                if i == self.checkbox.position() {
+                let styled = if i == self.checkbox.position() {
                     StyledGraphemes::from_iter([&StyledGraphemes::from(&self.cursor), &f(i), item])
                         .apply_style(self.active_item_style)
                 } else {
@@ -66,16 +92,33 @@ impl PaneFactory for State {
                         item,
                     ])
                     .apply_style(self.inactive_item_style)
-                }
+                };
+                (i, styled)
+            })
+            .flat_map(|(i, item)| {
+                item.matrixify(inner_width, height, 0)
+                    .0
+                    .into_iter()
+                    .map(move |row| (i, row))
             })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.checkbox.position() + height {
-                    acc.extend(rows);
-                }
-                (acc, pos + 1)
-            });
+            .collect()
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+        let rows = self.rows(width, height);
 
-        Pane::new(matrix.0, 0)
+        self.indicator.create_pane(
+            rows.into_iter().map(|(_, row)| row).collect(),
+            width,
+            height as u16,
+            self.checkbox.position(),
+            self.checkbox.items().len(),
+        )
     }
 }
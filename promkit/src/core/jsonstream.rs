@@ -25,6 +25,11 @@ impl JsonStream {
         &self.rows
     }
 
+    /// Returns the index of the row at the cursor, counting hidden rows as well.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
     /// Extracts a specified number of rows from the current position in JSON stream.
     pub fn extract_rows_from_current(&self, n: usize) -> Vec<Row> {
         self.rows.extract(self.position, n)
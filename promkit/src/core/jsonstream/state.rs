@@ -1,4 +1,4 @@
-use crate::{jsonz::format::RowFormatter, pane::Pane, PaneFactory};
+use crate::{indicator::Indicator, jsonz::format::RowFormatter, pane::Pane, PaneFactory};
 
 use super::JsonStream;
 
@@ -16,6 +16,22 @@ pub struct State {
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+
+    /// Scrollbar and position footer shown with the rows.
+    pub indicator: Indicator,
+}
+
+impl State {
+    /// Creates a state for the given stream with the default formatter,
+    /// whose other fields can be set with e.g. `State { lines: Some(10), ..State::new(stream) }`.
+    pub fn new(stream: JsonStream) -> Self {
+        Self {
+            stream,
+            formatter: Default::default(),
+            lines: None,
+            indicator: Default::default(),
+        }
+    }
 }
 
 impl PaneFactory for State {
@@ -26,8 +42,16 @@ impl PaneFactory for State {
         };
 
         let rows = self.stream.extract_rows_from_current(height);
-        let formatted_rows = self.formatter.format_for_terminal_display(&rows, width);
+        let formatted_rows = self
+            .formatter
+            .format_for_terminal_display(&rows, self.indicator.inner_width(width));
 
-        Pane::new(formatted_rows, 0)
+        self.indicator.create_pane(
+            formatted_rows,
+            width,
+            height as u16,
+            self.stream.position(),
+            self.stream.rows().len(),
+        )
     }
 }
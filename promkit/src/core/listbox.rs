@@ -33,14 +33,45 @@ impl Listbox {
         ))
     }
 
+    /// Creates a new `Listbox` from strings with markup tags
+    /// (see `StyledGraphemes::from_markup`), whose styles are kept in the items.
+    pub fn from_markup<E: AsRef<str>, I: IntoIterator<Item = E>>(items: I) -> Self {
+        Self(Cursor::new(
+            items
+                .into_iter()
+                .map(StyledGraphemes::from_markup)
+                .collect(),
+            0,
+            false,
+        ))
+    }
+
+    /// Creates a new `Listbox` from strings with ANSI escape sequences,
+    /// whose styles are kept in the items.
+    pub fn from_ansi<E: AsRef<str>, I: IntoIterator<Item = E>>(items: I) -> Self {
+        Self(Cursor::new(
+            items.into_iter().map(StyledGraphemes::from_ansi).collect(),
+            0,
+            false,
+        ))
+    }
+
     pub fn len(&self) -> usize {
         self.0.contents().len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.contents().is_empty()
+    }
+
     pub fn push_string(&mut self, item: String) {
         self.0.contents_mut().push(StyledGraphemes::from(item));
     }
 
+    pub fn push(&mut self, item: StyledGraphemes) {
+        self.0.contents_mut().push(item);
+    }
+
     /// Creates a new `Listbox` from a vector of `StyledGraphemes`.
     pub fn from_styled_graphemes(items: Vec<StyledGraphemes>) -> Self {
         Self(Cursor::new(items, 0, false))
@@ -88,6 +119,12 @@ impl Listbox {
         self.0.move_to_tail()
     }
 
+    /// Moves the cursor to the item at the given position in the listbox, if it exists.
+    /// Returns `true` if the cursor was moved, `false` otherwise.
+    pub fn move_to(&mut self, position: usize) -> bool {
+        self.0.move_to(position)
+    }
+
     pub fn is_tail(&self) -> bool {
         self.0.is_tail()
     }
@@ -1,4 +1,7 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, indicator::Indicator, pane::Pane,
+    PaneFactory,
+};
 
 use super::Listbox;
 
@@ -17,32 +20,65 @@ pub struct State {
     pub active_item_style: Option<ContentStyle>,
     /// Style for un-selected lines.
     pub inactive_item_style: Option<ContentStyle>,
+    /// Whether the styles above are applied only to the parts of the items
+    /// without a style of their own, e.g. to keep the colors of the items
+    /// created with `Listbox::from_ansi`. Otherwise they override the styles of the items.
+    pub keep_item_styles: bool,
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+
+    /// Scrollbar and position footer shown with the items.
+    pub indicator: Indicator,
 }
 
-impl PaneFactory for State {
-    fn create_pane(&self, width: u16, height: u16) -> Pane {
-        let height = match self.lines {
-            Some(lines) => lines.min(height as usize),
-            None => height as usize,
-        };
+impl Default for State {
+    /// Creates a state with an empty listbox, `❯ ` as the cursor and no styles.
+    fn default() -> Self {
+        Self {
+            listbox: Default::default(),
+            cursor: String::from("❯ "),
+            active_item_style: None,
+            inactive_item_style: None,
+            keep_item_styles: false,
+            lines: None,
+            indicator: Default::default(),
+        }
+    }
+}
 
-        let matrix = self
-            .listbox
+impl State {
+    /// Returns the index of the item drawn at the given row of the pane
+    /// created with the given width, if any.
+    pub fn index_at(&self, width: u16, row: usize) -> Option<usize> {
+        self.rows(width, row + 1).get(row).map(|(index, _)| *index)
+    }
+
+    fn apply_item_style(&self, item: StyledGraphemes, style: ContentStyle) -> StyledGraphemes {
+        if self.keep_item_styles {
+            item.apply_base_style(style)
+        } else {
+            item.apply_style(style)
+        }
+    }
+
+    /// Returns the rows of the items shown within the given size,
+    /// each with the index of its item.
+    fn rows(&self, width: u16, height: usize) -> Vec<(usize, StyledGraphemes)> {
+        let inner_width = self.indicator.inner_width(width) as usize;
+
+        self.listbox
             .items()
             .iter()
             .enumerate()
             .filter(|(i, _)| *i >= self.listbox.position() && *i < self.listbox.position() + height)
             .map(|(i, item)| {
-                if i == self.listbox.position() {
+                let styled = if i == self.listbox.position() {
                     let init =
                         StyledGraphemes::from_iter([&StyledGraphemes::from(&self.cursor), item]);
-                    if let Some(style) = &self.active_item_style {
-                        init.apply_style(*style)
-                    } else {
-                        init
+                    match &self.active_item_style {
+                        Some(style) => self.apply_item_style(init, *style),
+                        None => init,
                     }
                 } else {
                     let init = StyledGraphemes::from_iter([
@@ -51,21 +87,102 @@ impl PaneFactory for State {
                         ),
                         item,
                     ]);
-                    if let Some(style) = &self.inactive_item_style {
-                        init.apply_style(*style)
-                    } else {
-                        init
+                    match &self.inactive_item_style {
+                        Some(style) => self.apply_item_style(init, *style),
+                        None => init,
                     }
-                }
+                };
+                (i, styled)
             })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.listbox.position() + height {
-                    acc.extend(rows);
-                }
-                (acc, pos + 1)
-            });
-
-        Pane::new(matrix.0, 0)
+            .flat_map(|(i, item)| {
+                item.matrixify(inner_width, height, 0)
+                    .0
+                    .into_iter()
+                    .map(move |row| (i, row))
+            })
+            .collect()
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+        let rows = self.rows(width, height);
+
+        self.indicator.create_pane(
+            rows.into_iter().map(|(_, row)| row).collect(),
+            width,
+            height as u16,
+            self.listbox.position(),
+            self.listbox.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod create_pane {
+        use crate::{crossterm::style::Color, style::StyleBuilder};
+
+        use super::super::*;
+
+        fn red() -> ContentStyle {
+            StyleBuilder::new().fgc(Color::Red).build()
+        }
+
+        fn active() -> ContentStyle {
+            StyleBuilder::new().fgc(Color::DarkCyan).build()
+        }
+
+        fn inactive() -> ContentStyle {
+            StyleBuilder::new().fgc(Color::DarkGrey).build()
+        }
+
+        fn rows(keep_item_styles: bool) -> Vec<String> {
+            State {
+                listbox: Listbox::from_styled_graphemes(vec![
+                    StyledGraphemes::from_str("a", red()),
+                    StyledGraphemes::from("b"),
+                ]),
+                active_item_style: Some(active()),
+                inactive_item_style: Some(inactive()),
+                keep_item_styles,
+                ..Default::default()
+            }
+            .create_pane(10, 2)
+            .extract(2)
+            .iter()
+            .map(|row| row.to_ansi())
+            .collect()
+        }
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                vec![
+                    StyledGraphemes::from_str("❯ a", active()).to_ansi(),
+                    StyledGraphemes::from_str("  b", inactive()).to_ansi(),
+                ],
+                rows(false),
+            );
+        }
+
+        #[test]
+        fn test_with_keep_item_styles() {
+            assert_eq!(
+                vec![
+                    StyledGraphemes::from_iter([
+                        StyledGraphemes::from_str("❯ ", active()),
+                        StyledGraphemes::from_str("a", red()),
+                    ])
+                    .to_ansi(),
+                    StyledGraphemes::from_str("  b", inactive()).to_ansi(),
+                ],
+                rows(true),
+            );
+        }
     }
 }
@@ -35,8 +35,15 @@ impl<R: PaneFactory + Clone + 'static> Snapshot<R> {
         &self.init
     }
 
+    /// Returns a mutable reference to the initial state (`init`) of the renderer.
+    /// This allows for data to be added to the initial state after construction,
+    /// e.g. items streamed into a running prompt.
+    pub fn init_mut(&mut self) -> &mut R {
+        &mut self.init
+    }
+
     /// Returns a reference to the state of the renderer before any changes were applied (`before`).
-    pub fn borrow_before(&self) -> Ref<R> {
+    pub fn borrow_before(&self) -> Ref<'_, R> {
         self.before.borrow()
     }
 
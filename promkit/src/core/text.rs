@@ -27,6 +27,45 @@ impl<T: AsRef<str>> From<T> for Text {
 }
 
 impl Text {
+    /// Creates a new `Text` from a string with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn from_markup<T: AsRef<str>>(text: T) -> Self {
+        Self::from_lines(StyledGraphemes::from_markup(text))
+    }
+
+    /// Creates a new `Text` from a string with ANSI escape sequences,
+    /// keeping the styles of the sequences across lines.
+    pub fn from_ansi<T: AsRef<str>>(text: T) -> Self {
+        Self::from_lines(StyledGraphemes::from_ansi(text))
+    }
+
+    /// Splits the graphemes into lines, keeping the styles across them.
+    fn from_lines(graphemes: StyledGraphemes) -> Self {
+        let mut lines = vec![StyledGraphemes::default()];
+        for grapheme in graphemes.iter() {
+            match grapheme.as_str() {
+                "\n" | "\r\n" => lines.push(StyledGraphemes::default()),
+                _ => {
+                    if let Some(line) = lines.last_mut() {
+                        line.push_back(grapheme.clone());
+                    }
+                }
+            }
+        }
+        let lines = lines
+            .into_iter()
+            // Same as `Text::from`, for empty lines.
+            .map(|line| {
+                if line.is_empty() {
+                    StyledGraphemes::from("\0")
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Self(Cursor::new(lines, 0, false))
+    }
+
     /// Returns a reference to the vector of items in the listbox.
     pub fn items(&self) -> &Vec<StyledGraphemes> {
         self.0.contents()
@@ -49,3 +88,22 @@ impl Text {
         self.0.forward()
     }
 }
+
+#[cfg(test)]
+mod test {
+    mod from_ansi {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let text = Text::from_ansi("\x1b[31ma\r\n\nb\x1b[0m\nc");
+            assert_eq!(
+                vec!["\x1b[38;5;1ma\x1b[39m", "\0", "\x1b[38;5;1mb\x1b[39m", "c"],
+                text.items()
+                    .iter()
+                    .map(|line| line.to_ansi())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{crossterm::style::ContentStyle, pane::Pane, PaneFactory};
+use crate::{crossterm::style::ContentStyle, grapheme::Wrap, pane::Pane, PaneFactory};
 
 use super::Text;
 
@@ -6,7 +6,7 @@ use super::Text;
 ///
 /// This state encapsulates the properties and
 /// behaviors specific to text handling,
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct State {
     /// The text to be rendered.
     pub text: Text,
@@ -14,8 +14,16 @@ pub struct State {
     /// Style for the text string.
     pub style: ContentStyle,
 
+    /// Whether `style` is applied only to the parts of the text without a style of their own,
+    /// e.g. to keep the colors of a text created with `Text::from_ansi`.
+    /// Otherwise it overrides the styles of the text.
+    pub keep_styles: bool,
+
     /// Maximum number of lines to display.
     pub lines: Option<usize>,
+
+    /// How lines longer than the width are wrapped.
+    pub wrap: Wrap,
 }
 
 impl State {
@@ -24,6 +32,17 @@ impl State {
     }
 }
 
+#[cfg(test)]
+impl State {
+    /// Creates a state of the given text with the default settings, for tests of panes.
+    pub(crate) fn with_text(text: &str) -> Self {
+        Self {
+            text: Text::from(text),
+            ..Default::default()
+        }
+    }
+}
+
 impl PaneFactory for State {
     fn create_pane(&self, width: u16, height: u16) -> Pane {
         let height = match self.lines {
@@ -37,9 +56,15 @@ impl PaneFactory for State {
             .iter()
             .enumerate()
             .filter(|(i, _)| *i >= self.text.position() && *i < self.text.position() + height)
-            .map(|(_, item)| item.clone().apply_style(self.style))
+            .map(|(_, item)| {
+                if self.keep_styles {
+                    item.clone().apply_base_style(self.style)
+                } else {
+                    item.clone().apply_style(self.style)
+                }
+            })
             .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
+                let rows = item.matrixify_with(width as usize, height, 0, self.wrap).0;
                 if pos < self.text.position() + height {
                     acc.extend(rows);
                 }
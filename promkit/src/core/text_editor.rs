@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::{
     core::cursor::Cursor,
     grapheme::{StyledGrapheme, StyledGraphemes},
@@ -23,6 +25,10 @@ pub enum Mode {
 /// A text editor that supports basic editing operations
 /// such as insert, delete, and overwrite.
 /// It utilizes a cursor to navigate and manipulate the text.
+///
+/// The text is held as extended grapheme clusters, so that the cursor moves over,
+/// and erase removes, e.g. an emoji ZWJ sequence or a letter
+/// with combining accents as a whole.
 #[derive(Clone)]
 pub struct TextEditor(Cursor<StyledGraphemes>);
 
@@ -41,8 +47,9 @@ impl TextEditor {
     pub fn new<S: AsRef<str>>(s: S) -> Self {
         let mut buf = s.as_ref().to_owned();
         buf.push(' ');
-        let pos = buf.len() - 1;
-        Self(Cursor::new(StyledGraphemes::from(buf), pos, false))
+        let graphemes = StyledGraphemes::from(buf);
+        let pos = graphemes.len() - 1;
+        Self(Cursor::new(graphemes, pos, false))
     }
 
     /// Returns the current text including the cursor.
@@ -62,40 +69,88 @@ impl TextEditor {
         self.0.position()
     }
 
-    /// Masks all characters except the cursor with the specified mask character.
+    /// Masks all graphemes except the cursor with the specified mask character.
     pub fn masking(&self, mask: char) -> StyledGraphemes {
-        self.text()
-            .chars()
-            .into_iter()
+        let text = self.text();
+        let last = text.len() - 1;
+        text.iter()
             .enumerate()
-            .map(|(i, c)| StyledGrapheme::from(if i == self.text().len() - 1 { c } else { mask }))
+            .map(|(i, g)| {
+                if i == last {
+                    g.clone()
+                } else {
+                    StyledGrapheme::from(mask)
+                }
+            })
             .collect::<StyledGraphemes>()
     }
 
     /// Replaces the current text with new text and positions the cursor at the end.
     pub fn replace(&mut self, new: &str) {
-        let mut buf = new.to_owned();
-        buf.push(' ');
-        let pos = buf.len() - 1;
-        *self = Self(Cursor::new(StyledGraphemes::from(buf), pos, false));
+        *self = Self::new(new);
     }
 
     /// Inserts a character at the current cursor position.
+    ///
+    /// A character that extends the grapheme before the cursor,
+    /// such as a combining accent or a zero width joiner, is merged into it.
     pub fn insert(&mut self, ch: char) {
-        let pos = self.position();
-        self.0.contents_mut().insert(pos, StyledGrapheme::from(ch));
-        self.forward();
+        self.insert_str(ch.encode_utf8(&mut [0; 4]));
     }
 
     pub fn insert_chars(&mut self, vch: &Vec<char>) {
+        let mut s = String::new();
         for ch in vch {
-            self.insert(*ch);
+            s.push(*ch);
         }
+        self.insert_str(&s);
     }
 
-    /// Overwrites the character at the current cursor position with the specified character.
+    /// Inserts a string at the current cursor position,
+    /// and moves the cursor to the end of the inserted string.
+    ///
+    /// The text before the cursor is split into graphemes again with the string,
+    /// so that the string is merged with the grapheme before the cursor if they form one.
+    /// The graphemes after the cursor are kept as they are.
+    fn insert_str(&mut self, s: &str) {
+        let pos = self.position();
+        let contents = self.0.contents();
+        let mut head = contents
+            .iter()
+            .take(pos)
+            .map(|g| g.as_str())
+            .collect::<String>();
+        head.push_str(s);
+
+        let mut text = StyledGraphemes::from(head);
+        let position = text.len();
+        text.extend(contents.iter().skip(pos).cloned());
+        self.0 = Cursor::new(text, position, false);
+    }
+
+    /// Inserts pasted text at the current cursor position in one go.
+    ///
+    /// Since the editor holds a single line, line breaks and tabs are replaced
+    /// with spaces, and other control characters are dropped.
+    pub fn paste(&mut self, text: &str) {
+        let chars = text
+            .replace("\r\n", "\n")
+            .chars()
+            .filter_map(|ch| match ch {
+                '\n' | '\r' | '\t' => Some(' '),
+                ch if ch.is_control() => None,
+                ch => Some(ch),
+            })
+            .collect::<Vec<_>>();
+        self.insert_chars(&chars);
+    }
+
+    /// Overwrites the grapheme at the current cursor position with the specified character.
+    ///
+    /// A character that extends the grapheme before the cursor is merged into it
+    /// instead, in the same way as `insert`.
     pub fn overwrite(&mut self, ch: char) {
-        if self.0.is_tail() {
+        if self.0.is_tail() || self.extends_previous(ch) {
             self.insert(ch)
         } else {
             let pos = self.position();
@@ -112,7 +167,19 @@ impl TextEditor {
         }
     }
 
-    /// Erases the character before the cursor position.
+    /// Returns whether the character forms a single grapheme
+    /// with the grapheme before the cursor.
+    fn extends_previous(&self, ch: char) -> bool {
+        let pos = self.position();
+        pos > 0
+            && self
+                .0
+                .contents()
+                .get(pos - 1)
+                .is_some_and(|g| format!("{}{}", g.as_str(), ch).graphemes(true).count() == 1)
+    }
+
+    /// Erases the grapheme before the cursor position.
     pub fn erase(&mut self) {
         if !self.0.is_head() {
             self.backward();
@@ -142,12 +209,11 @@ impl TextEditor {
     fn find_previous_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
         let current_position = self.position();
         self.text()
-            .chars()
             .iter()
             .enumerate()
             .filter(|&(i, _)| i < current_position.saturating_sub(1))
             .rev()
-            .find(|&(_, c)| word_break_chars.contains(c))
+            .find(|&(_, g)| is_word_break(g, word_break_chars))
             .map(|(i, _)| i + 1)
             .unwrap_or(0)
     }
@@ -168,11 +234,10 @@ impl TextEditor {
     fn find_next_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
         let current_position = self.position();
         self.text()
-            .chars()
             .iter()
             .enumerate()
             .filter(|&(i, _)| i > current_position)
-            .find(|&(_, c)| word_break_chars.contains(c))
+            .find(|&(_, g)| is_word_break(g, word_break_chars))
             .map(|(i, _)| {
                 if i < self.0.contents().len() - 1 {
                     i + 1
@@ -220,6 +285,13 @@ impl TextEditor {
     }
 }
 
+/// Returns whether the grapheme is one of the characters in `word_break_chars`.
+fn is_word_break(grapheme: &StyledGrapheme, word_break_chars: &HashSet<char>) -> bool {
+    grapheme
+        .as_char()
+        .is_some_and(|ch| word_break_chars.contains(&ch))
+}
+
 #[cfg(test)]
 mod test {
     use crate::{core::cursor::Cursor, grapheme::StyledGraphemes};
@@ -293,6 +365,49 @@ mod test {
         }
     }
 
+    mod insert_with_clusters {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let mut txt = TextEditor::new("cafe");
+            txt.insert('\u{301}');
+            assert_eq!("cafe\u{301} ", txt.text().to_string());
+            assert_eq!(5, txt.text().len());
+            assert_eq!(4, txt.position());
+
+            txt.backward();
+            txt.erase();
+            assert_eq!("cae\u{301} ", txt.text().to_string());
+            txt.move_to_tail();
+            txt.erase();
+            assert_eq!("ca ", txt.text().to_string());
+        }
+
+        #[test]
+        fn test_with_zwj_sequence() {
+            let mut txt = TextEditor::new("👨");
+            txt.insert_chars(&vec!['\u{200d}', '👩']);
+            assert_eq!(2, txt.text().len());
+            assert_eq!(1, txt.position());
+            txt.erase();
+            assert_eq!(" ", txt.text().to_string());
+        }
+
+        #[test]
+        fn test_before_flag() {
+            // A regional indicator inserted before a flag does not pair with it.
+            let mut txt = TextEditor::new("🇯🇵");
+            txt.move_to_head();
+            txt.insert('🇺');
+            assert_eq!(
+                vec!["🇺", "🇯🇵", " "],
+                txt.text().iter().map(|g| g.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!(1, txt.position());
+        }
+    }
+
     mod find_previous_nearest_index {
         use std::collections::HashSet;
 
@@ -396,6 +511,25 @@ mod test {
         }
     }
 
+    mod paste {
+        use crate::text_editor::test::new_with_position;
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(
+                String::from("ab "),
+                1, // indicate `b`.
+            );
+            let new = new_with_position(
+                String::from("ac d e fb "),
+                8, // indicate `b`.
+            );
+            txt.paste("c\r\nd\te\u{1b}\nf");
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+    }
+
     mod overwrite {
         use crate::text_editor::test::new_with_position;
 
@@ -1,10 +1,15 @@
 use std::collections::HashSet;
 
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::StyledGraphemes,
+    pane::{Caret, CursorShape, Pane},
+    PaneFactory,
+};
 
 use super::{History, Mode, TextEditor};
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct State {
     /// The `TextEditor` component to be rendered.
     pub texteditor: TextEditor,
@@ -19,6 +24,7 @@ pub struct State {
     /// Style applied to the prompt string.
     pub prefix_style: ContentStyle,
     /// Style applied to the currently selected character.
+    /// Not applied when the real terminal cursor is shown (see `cursor_shape`).
     pub active_char_style: ContentStyle,
     /// Style applied to characters that are not currently selected.
     pub inactive_char_style: ContentStyle,
@@ -29,6 +35,9 @@ pub struct State {
     pub word_break_chars: HashSet<char>,
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+    /// Shape of the real terminal cursor shown at the current position,
+    /// or `None` to hide it and paint the position with `active_char_style` instead.
+    pub cursor_shape: Option<CursorShape>,
 }
 
 impl PaneFactory for State {
@@ -36,6 +45,7 @@ impl PaneFactory for State {
         let mut buf = StyledGraphemes::default();
 
         let mut styled_prefix = StyledGraphemes::from_str(&self.prefix, self.prefix_style);
+        let prefix_len = styled_prefix.len();
 
         buf.append(&mut styled_prefix);
 
@@ -44,9 +54,12 @@ impl PaneFactory for State {
             None => self.texteditor.text(),
         };
 
-        let mut styled = text
-            .apply_style(self.inactive_char_style)
-            .apply_style_at(self.texteditor.position(), self.active_char_style);
+        let mut styled = match self.cursor_shape {
+            Some(_) => text.apply_style(self.inactive_char_style),
+            None => text
+                .apply_style(self.inactive_char_style)
+                .apply_style_at(self.texteditor.position(), self.active_char_style),
+        };
 
         buf.append(&mut styled);
 
@@ -55,14 +68,13 @@ impl PaneFactory for State {
             None => height as usize,
         };
 
-        let (matrix, offset) = buf.matrixify(
-            width as usize,
-            height,
-            (StyledGraphemes::from_str(&self.prefix, self.prefix_style).widths()
-                + self.texteditor.position())
-                / width as usize,
-        );
+        let (row, column) = buf.locate(width as usize, prefix_len + self.texteditor.position());
+        let (matrix, offset) = buf.matrixify(width as usize, height, row);
 
-        Pane::new(matrix, offset)
+        Pane::new(matrix, offset).with_caret(Some(Caret {
+            row: offset,
+            column,
+            shape: self.cursor_shape,
+        }))
     }
 }
@@ -49,6 +49,30 @@ impl Tree {
         }
     }
 
+    /// Returns whether the tree has a node at the given IDs
+    /// of the nodes from the root, including the root itself.
+    pub fn contains(&self, ids: &[String]) -> bool {
+        let Some((first, rest)) = ids.split_first() else {
+            return false;
+        };
+        let mut node = &self.root;
+        if node.id() != first {
+            return false;
+        }
+        for id in rest {
+            match node {
+                Node::NonLeaf { children, .. } => {
+                    match children.iter().find(|child| child.id() == id) {
+                        Some(child) => node = child,
+                        None => return false,
+                    }
+                }
+                Node::Leaf(_) => return false,
+            }
+        }
+        true
+    }
+
     /// Toggles the state of the current node and updates the cursor position accordingly.
     pub fn toggle(&mut self) {
         let path = match self.cursor.contents()[self.position()].clone() {
@@ -82,4 +106,10 @@ impl Tree {
     pub fn move_to_tail(&mut self) {
         self.cursor.move_to_tail()
     }
+
+    /// Moves the cursor to the node at the given position in the tree, if it exists.
+    /// Returns `true` if the cursor was moved, `false` otherwise.
+    pub fn move_to(&mut self, position: usize) -> bool {
+        self.cursor.move_to(position)
+    }
 }
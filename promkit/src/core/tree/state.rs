@@ -1,4 +1,7 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, indicator::Indicator, pane::Pane,
+    PaneFactory,
+};
 
 use super::{Kind, Tree};
 
@@ -26,6 +29,9 @@ pub struct State {
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
 
+    /// Scrollbar and position footer shown with the items.
+    pub indicator: Indicator,
+
     /// The number of spaces used for indenting child items in the tree.
     /// This value determines how much horizontal space is used to visually
     /// represent the hierarchical structure of the tree. Each level of
@@ -33,8 +39,33 @@ pub struct State {
     pub indent: usize,
 }
 
-impl PaneFactory for State {
-    fn create_pane(&self, width: u16, height: u16) -> Pane {
+impl State {
+    /// Creates a state for the given tree, with `▶︎ ` and `▼ ` as the symbols,
+    /// no styles and an indent of 2, whose other fields can be set
+    /// with e.g. `State { indent: 4, ..State::new(tree) }`.
+    pub fn new(tree: Tree) -> Self {
+        Self {
+            tree,
+            folded_symbol: String::from("▶︎ "),
+            unfolded_symbol: String::from("▼ "),
+            active_item_style: Default::default(),
+            inactive_item_style: Default::default(),
+            lines: None,
+            indicator: Default::default(),
+            indent: 2,
+        }
+    }
+
+    /// Returns the index of the item drawn at the given row of the pane
+    /// created with the given width, if any.
+    pub fn index_at(&self, width: u16, row: usize) -> Option<usize> {
+        self.rows(width, row + 1).get(row).map(|(index, _)| *index)
+    }
+
+    /// Returns the rows of the items shown within the given size,
+    /// each with the index of its item.
+    fn rows(&self, width: u16, height: usize) -> Vec<(usize, StyledGraphemes)> {
+        let inner_width = self.indicator.inner_width(width) as usize;
         let symbol = |kind: &Kind| -> &str {
             match kind {
                 Kind::Folded { .. } => &self.folded_symbol,
@@ -54,19 +85,13 @@ impl PaneFactory for State {
             }
         };
 
-        let height = match self.lines {
-            Some(lines) => lines.min(height as usize),
-            None => height as usize,
-        };
-
-        let matrix = self
-            .tree
+        self.tree
             .kinds()
             .iter()
             .enumerate()
             .filter(|(i, _)| *i >= self.tree.position() && *i < self.tree.position() + height)
             .map(|(i, kind)| {
-                if i == self.tree.position() {
+                let styled = if i == self.tree.position() {
                     StyledGraphemes::from_str(
                         format!("{}{}{}", symbol(kind), " ".repeat(indent(kind)), id(kind),),
                         self.active_item_style,
@@ -81,16 +106,33 @@ impl PaneFactory for State {
                         ),
                         self.inactive_item_style,
                     )
-                }
+                };
+                (i, styled)
+            })
+            .flat_map(|(i, item)| {
+                item.matrixify(inner_width, height, 0)
+                    .0
+                    .into_iter()
+                    .map(move |row| (i, row))
             })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.tree.position() + height {
-                    acc.extend(rows);
-                }
-                (acc, pos + 1)
-            });
+            .collect()
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+        let rows = self.rows(width, height);
 
-        Pane::new(matrix.0, 0)
+        self.indicator.create_pane(
+            rows.into_iter().map(|(_, row)| row).collect(),
+            width,
+            height as u16,
+            self.tree.position(),
+            self.tree.kinds().len(),
+        )
     }
 }
@@ -0,0 +1,107 @@
+use std::{fmt, io};
+
+/// A specialized `Result` type for prompts.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors that can occur while running a prompt.
+///
+/// Keymaps and renderers return `anyhow::Result`, so that custom
+/// implementations can propagate any error. These errors are converted into
+/// this type when they leave `Prompt::run`, and a variant of this type
+/// returned from a keymap (e.g. `Err(Error::Interrupted.into())`)
+/// is passed through as is.
+///
+/// ```ignore
+/// match Readline::default().prompt()?.run() {
+///     Ok(text) => println!("result: {:?}", text),
+///     Err(promkit::Error::Interrupted) => std::process::exit(130),
+///     Err(e) => return Err(e.into()),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// The prompt was aborted by the user (Ctrl+C).
+    Interrupted,
+    /// The user signaled the end of input (Ctrl+D).
+    Eof,
+    /// An I/O error occurred while reading events or drawing the prompt.
+    Io(io::Error),
+    /// The input was rejected by a validator, with the error message.
+    Validation(String),
+    /// Any other error returned by a renderer.
+    Other(anyhow::Error),
+}
+
+impl Error {
+    /// Returns the conventional exit code for a process terminated by this error,
+    /// i.e. 130 for `Interrupted` as with SIGINT, and 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Interrupted => 130,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Interrupted => write!(f, "interrupted"),
+            Error::Eof => write!(f, "end of input"),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Validation(message) => write!(f, "validation failed: {}", message),
+            Error::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<Error>() {
+            Ok(e) => e,
+            Err(e) => match e.downcast::<io::Error>() {
+                Ok(e) => Error::Io(e),
+                Err(e) => Error::Other(e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod from {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            assert!(matches!(
+                Error::from(anyhow::Error::from(Error::Interrupted)),
+                Error::Interrupted
+            ));
+            assert!(matches!(
+                Error::from(anyhow::Error::from(io::Error::other("io"))),
+                Error::Io(_)
+            ));
+            assert!(matches!(
+                Error::from(anyhow::anyhow!("other")),
+                Error::Other(_)
+            ));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for reading answers from plain text input.
+//!
+//! When stdin or the output is not a terminal (e.g. in CI, with piped input,
+//! or with `Prompt::tty(false)`),
+//! `Prompt::run` does not draw anything and instead calls
+//! `Renderer::fallback` with stdin. The presets read their answers
+//! line by line with the helpers in this module.
+//! The same parsing applies to pre-seeded answers, see [`crate::answers`].
+use std::io::BufRead;
+
+use crate::Error;
+
+/// Reads a single line from the input, without the trailing line break.
+///
+/// # Returns
+///
+/// Returns `Error::Eof` if the input has no more lines.
+pub fn read_line(input: &mut dyn BufRead) -> anyhow::Result<String> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(Error::Eof.into());
+    }
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(line)
+}
+
+/// Finds the index of the item chosen by the answer,
+/// which is either the exact text of an item or its zero-based index.
+///
+/// # Returns
+///
+/// Returns `Error::Validation` if no item matches the answer.
+pub fn select(items: &[String], answer: &str) -> anyhow::Result<usize> {
+    let answer = answer.trim();
+    items
+        .iter()
+        .position(|item| item == answer)
+        .or_else(|| {
+            answer
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < items.len())
+        })
+        .ok_or_else(|| Error::Validation(format!("no such item: {}", answer)).into())
+}
+
+#[cfg(test)]
+mod test {
+    mod read_line {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let mut input = "a\r\nb\nc".as_bytes();
+            assert_eq!("a", read_line(&mut input).unwrap());
+            assert_eq!("b", read_line(&mut input).unwrap());
+            assert_eq!("c", read_line(&mut input).unwrap());
+            assert!(matches!(
+                read_line(&mut input).map_err(Error::from),
+                Err(Error::Eof)
+            ));
+        }
+    }
+
+    mod select {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let items = vec![String::from("a"), String::from("1"), String::from("b")];
+            assert_eq!(2, select(&items, "b").unwrap());
+            assert_eq!(1, select(&items, "1").unwrap());
+            assert_eq!(0, select(&items, "0").unwrap());
+            assert!(matches!(
+                select(&items, "3").map_err(Error::from),
+                Err(Error::Validation(_))
+            ));
+        }
+    }
+}
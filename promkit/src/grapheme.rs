@@ -2,36 +2,60 @@ use std::{
     collections::VecDeque,
     fmt,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
-use unicode_width::UnicodeWidthChar;
+use unicode_linebreak::linebreaks;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::crossterm::style::{Attribute, ContentStyle};
 
-/// Represents a single grapheme (character) with its display width and optional styling.
+mod ansi;
+mod markup;
+
+/// How `StyledGraphemes::matrixify_with` breaks graphemes into rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// Breaks at the column where the width runs out, even within a word.
+    #[default]
+    Char,
+    /// Breaks at whitespace or other line break opportunities of Unicode (UAX #14),
+    /// falling back to `Char` for words longer than the width.
+    /// Whitespace at the ends of wrapped rows is dropped.
+    Word {
+        /// The number of columns that the rows after the first are indented by.
+        indent: usize,
+    },
+}
+
+/// Represents a single grapheme with its display width and optional styling.
+///
+/// A grapheme is an extended grapheme cluster, i.e. what is perceived as a single
+/// character, such as `é` written with a combining accent, a flag or an emoji
+/// ZWJ sequence, which may consist of several `char`s. Its width is computed
+/// for the cluster as a whole.
 ///
-/// This structure is similar to `Grapheme` but includes styling information directly.
+/// A grapheme may also carry the target of a hyperlink, which terminals
+/// supporting OSC 8 make clickable. It does not affect the width.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StyledGrapheme {
-    ch: char,
+    cluster: String,
     width: usize,
     style: ContentStyle,
+    link: Option<Arc<str>>,
 }
 
 impl From<char> for StyledGrapheme {
     fn from(ch: char) -> Self {
-        Self {
-            ch,
-            width: UnicodeWidthChar::width(ch).unwrap_or(0),
-            style: ContentStyle::default(),
-        }
+        Self::new(ch, ContentStyle::default())
     }
 }
 
 impl fmt::Display for StyledGraphemes {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for styled_grapheme in self.iter() {
-            write!(f, "{}", styled_grapheme.ch)?;
+            write!(f, "{}", styled_grapheme.cluster)?;
         }
         Ok(())
     }
@@ -40,9 +64,49 @@ impl fmt::Display for StyledGraphemes {
 impl StyledGrapheme {
     pub fn new(ch: char, style: ContentStyle) -> Self {
         Self {
-            ch,
+            cluster: ch.to_string(),
             width: UnicodeWidthChar::width(ch).unwrap_or(0),
             style,
+            link: None,
+        }
+    }
+
+    /// Creates a grapheme from a string that is expected to be
+    /// a single extended grapheme cluster.
+    pub fn from_cluster<S: AsRef<str>>(cluster: S, style: ContentStyle) -> Self {
+        let cluster = cluster.as_ref();
+        let mut chars = cluster.chars();
+        match (chars.next(), chars.next()) {
+            // Single characters are measured as `char`s,
+            // so that control characters take no cells.
+            (Some(ch), None) => Self::new(ch, style),
+            // So do clusters of control characters, i.e. `\r\n`.
+            _ if cluster.chars().all(char::is_control) => Self {
+                cluster: cluster.to_string(),
+                width: 0,
+                style,
+                link: None,
+            },
+            _ => Self {
+                cluster: cluster.to_string(),
+                width: UnicodeWidthStr::width(cluster),
+                style,
+                link: None,
+            },
+        }
+    }
+
+    /// Returns the grapheme cluster as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.cluster
+    }
+
+    /// Returns the character of the grapheme if it consists of a single `char`.
+    pub fn as_char(&self) -> Option<char> {
+        let mut chars = self.cluster.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
         }
     }
 
@@ -53,6 +117,11 @@ impl StyledGrapheme {
     pub fn apply_style(&mut self, style: ContentStyle) {
         self.style = style;
     }
+
+    /// Returns the target of the hyperlink of the grapheme, if any.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
 }
 
 /// A collection of `StyledGrapheme` instances.
@@ -114,24 +183,29 @@ impl<S: AsRef<str>> From<S> for StyledGraphemes {
 impl fmt::Debug for StyledGraphemes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for styled_grapheme in self.iter() {
-            write!(f, "{}", styled_grapheme.ch)?;
+            write!(f, "{}", styled_grapheme.cluster)?;
         }
         Ok(())
     }
 }
 
 impl StyledGraphemes {
+    /// Splits the string into extended grapheme clusters with the given style.
     pub fn from_str<S: AsRef<str>>(string: S, style: ContentStyle) -> Self {
         string
             .as_ref()
-            .chars()
-            .map(|ch| StyledGrapheme::new(ch, style))
+            .graphemes(true)
+            .map(|cluster| StyledGrapheme::from_cluster(cluster, style))
             .collect()
     }
 
-    /// Returns a `Vec<char>` containing the characters of all `Grapheme` instances in the collection.
+    /// Returns a `Vec<char>` containing the characters of all `Grapheme` instances in the collection,
+    /// where a grapheme made of several characters contributes all of them.
     pub fn chars(&self) -> Vec<char> {
-        self.0.iter().map(|grapheme| grapheme.ch).collect()
+        self.0
+            .iter()
+            .flat_map(|grapheme| grapheme.cluster.chars())
+            .collect()
     }
 
     /// Calculates the total display width of all `Grapheme` instances in the collection.
@@ -139,10 +213,32 @@ impl StyledGraphemes {
         self.0.iter().map(|grapheme| grapheme.width).sum()
     }
 
+    /// Cuts the graphemes to the given width, ending them with `…` if they are cut.
+    pub(crate) fn truncate(mut self, width: usize) -> Self {
+        if self.widths() <= width {
+            return self;
+        }
+        let mut used = 0;
+        let kept = self
+            .0
+            .iter()
+            .take_while(|grapheme| {
+                used += grapheme.width;
+                used < width
+            })
+            .count();
+        let style = self.0[kept].style;
+        self.0.truncate(kept);
+        if width > 0 {
+            self.0.push_back(StyledGrapheme::new('…', style));
+        }
+        self
+    }
+
     /// Replaces all occurrences of a substring `from` with another substring `to` within the `StyledGraphemes`.
     pub fn replace<S: AsRef<str>>(mut self, from: S, to: S) -> Self {
-        let from_len = from.as_ref().chars().count();
-        let to_len = to.as_ref().chars().count();
+        let from_len = from.as_ref().graphemes(true).count();
+        let to_len = to.as_ref().graphemes(true).count();
 
         let mut offset = 0;
         let diff = from_len.abs_diff(to_len);
@@ -184,6 +280,33 @@ impl StyledGraphemes {
         self
     }
 
+    /// Applies a given style to the `StyledGrapheme` instances without a style of their own,
+    /// keeping the styles of the others (e.g. those parsed with `StyledGraphemes::from_ansi`).
+    pub fn apply_base_style(mut self, style: ContentStyle) -> Self {
+        for grapheme in &mut self.0 {
+            if grapheme.style == ContentStyle::default() {
+                grapheme.apply_style(style);
+            }
+        }
+        self
+    }
+
+    /// Makes all `StyledGrapheme` instances within the collection a hyperlink to the target,
+    /// e.g. a URL, or a `file://` URL for a path.
+    pub fn hyperlink<S: AsRef<str>>(mut self, target: S) -> Self {
+        // Control characters would terminate the escape sequence of the hyperlink.
+        let link: Arc<str> = target
+            .as_ref()
+            .chars()
+            .filter(|ch| !ch.is_control())
+            .collect::<String>()
+            .into();
+        for grapheme in &mut self.0 {
+            grapheme.link = Some(link.clone());
+        }
+        self
+    }
+
     /// Applies a given style to a specific `StyledGrapheme` at the specified index.
     pub fn apply_style_at(mut self, idx: usize, style: ContentStyle) -> Self {
         if let Some(grapheme) = self.0.get_mut(idx) {
@@ -201,14 +324,14 @@ impl StyledGraphemes {
 
         let mut indices = Vec::new();
         let mut pos = 0;
-        let query_chars: Vec<char> = query_str.chars().collect();
-        let query_len = query_chars.len();
+        let query_clusters: Vec<&str> = query_str.graphemes(true).collect();
+        let query_len = query_clusters.len();
 
         // Iterate through each grapheme in self
         while pos + query_len <= self.0.len() {
             let mut match_found = true;
-            for (i, query_char) in query_chars.iter().enumerate() {
-                if self.0[pos + i].ch != *query_char {
+            for (i, query_cluster) in query_clusters.iter().enumerate() {
+                if self.0[pos + i].cluster != *query_cluster {
                     match_found = false;
                     break;
                 }
@@ -233,7 +356,6 @@ impl StyledGraphemes {
     ///     - with the style applied to all occurrences of the query if the query is found.
     ///     - unchanged if the query string is empty.
     /// - `None`: if the query string is not found in the collection.
-
     pub fn highlight<S: AsRef<str>>(mut self, query: S, style: ContentStyle) -> Option<Self> {
         let query_str = query.as_ref();
         if query_str.is_empty() {
@@ -245,7 +367,7 @@ impl StyledGraphemes {
             return None;
         }
 
-        let query_len = query_str.chars().count();
+        let query_len = query_str.graphemes(true).count();
 
         for &start_index in &indices {
             for i in start_index..start_index + query_len {
@@ -281,6 +403,42 @@ impl StyledGraphemes {
         height: usize,
         offset: usize,
     ) -> (Vec<StyledGraphemes>, usize) {
+        self.matrixify_with(width, height, offset, Wrap::Char)
+    }
+
+    /// Same as `matrixify`, but breaks the graphemes into rows as specified by `wrap`.
+    pub fn matrixify_with(
+        &self,
+        width: usize,
+        height: usize,
+        offset: usize,
+        wrap: Wrap,
+    ) -> (Vec<StyledGraphemes>, usize) {
+        let mut all = match wrap {
+            Wrap::Char => self.wrap_chars(width),
+            Wrap::Word { indent } => self.wrap_words(width, indent),
+        };
+
+        if all.is_empty() {
+            return (vec![], 0);
+        }
+
+        let mut offset = std::cmp::min(offset, all.len().saturating_sub(1));
+
+        // Adjust the start and end rows based on the offset and height
+        while all.len() > height && offset < all.len() {
+            if offset > 0 {
+                all.pop_front();
+                offset -= 1;
+            } else {
+                all.pop_back();
+            }
+        }
+
+        (Vec::from(all), offset)
+    }
+
+    fn wrap_chars(&self, width: usize) -> VecDeque<StyledGraphemes> {
         let mut all = VecDeque::new();
         let mut row = StyledGraphemes::default();
         for styled in self.iter() {
@@ -299,24 +457,102 @@ impl StyledGraphemes {
         if !row.is_empty() {
             all.push_back(row);
         }
+        all
+    }
 
-        if all.is_empty() {
-            return (vec![], 0);
+    fn wrap_words(&self, width: usize, indent: usize) -> VecDeque<StyledGraphemes> {
+        let breakable = self.break_opportunities();
+        let indent = if indent < width { indent } else { 0 };
+        let is_whitespace = |g: &StyledGrapheme| g.cluster.chars().all(char::is_whitespace);
+
+        let mut all = VecDeque::new();
+        let mut row = StyledGraphemes::default();
+        // The number of graphemes at the head of the row for the indent.
+        let mut head = 0;
+        // Where the row may be split, i.e. right before its last break opportunity.
+        let mut last_break = None;
+        for (i, styled) in self.iter().enumerate() {
+            if width < styled.width {
+                continue;
+            }
+            if breakable[i] && row.len() > head {
+                last_break = Some(row.len());
+            }
+
+            if row.widths() + styled.width > width {
+                let mut tail = StyledGraphemes::default();
+                if !is_whitespace(styled) {
+                    if let Some(at) = last_break {
+                        // The rest of the word is moved to the next row only if it fits there.
+                        let rest = row.iter().skip(at).map(|g| g.width).sum::<usize>();
+                        if indent + rest + styled.width <= width {
+                            tail.0 = row.0.split_off(at);
+                        }
+                    }
+                }
+                while row.len() > head && row.back().is_some_and(is_whitespace) {
+                    row.pop_back();
+                }
+                // A row of only whitespace, e.g. leading spaces, is reused rather than pushed.
+                if row.len() > head {
+                    all.push_back(row);
+                    row = StyledGraphemes::from(" ".repeat(indent));
+                    head = row.len();
+                }
+                row.append(&mut tail);
+                last_break = None;
+            }
+
+            // Whitespace at the start of wrapped rows is dropped.
+            if row.len() == head && !all.is_empty() && is_whitespace(styled) {
+                continue;
+            }
+            row.push_back(styled.clone());
         }
+        if row.len() > head {
+            all.push_back(row);
+        }
+        all
+    }
 
-        let mut offset = std::cmp::min(offset, all.len().saturating_sub(1));
+    /// Returns whether a row may break right before each grapheme.
+    fn break_opportunities(&self) -> Vec<bool> {
+        let mut starts = Vec::with_capacity(self.len());
+        let mut text = String::new();
+        for styled in self.iter() {
+            starts.push(text.len());
+            text.push_str(&styled.cluster);
+        }
 
-        // Adjust the start and end rows based on the offset and height
-        while all.len() > height && offset < all.len() {
-            if offset > 0 {
-                all.pop_front();
-                offset -= 1;
-            } else {
-                all.pop_back();
+        let mut ret = vec![false; self.len()];
+        for (position, _) in linebreaks(&text) {
+            if let Ok(i) = starts.binary_search(&position) {
+                ret[i] = true;
             }
         }
+        ret
+    }
 
-        (Vec::from(all), offset)
+    /// Returns the row and the column (in cells) of the grapheme at the given index
+    /// when the graphemes are organized into rows by `matrixify` with the specified width.
+    /// If the index is out of range, the position right after the last grapheme is returned.
+    pub fn locate(&self, width: usize, index: usize) -> (usize, usize) {
+        let (mut row, mut column, mut count) = (0, 0, 0);
+        for (i, styled) in self.iter().enumerate() {
+            if count > 0 && width < column + styled.width {
+                row += 1;
+                column = 0;
+                count = 0;
+            }
+            if i == index {
+                return (row, column);
+            }
+            if width >= styled.width {
+                column += styled.width;
+                count += 1;
+            }
+        }
+        (row, column)
     }
 }
 
@@ -326,13 +562,32 @@ pub struct StyledGraphemesDisplay<'a> {
 
 impl<'a> fmt::Display for StyledGraphemesDisplay<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut link = None;
         for styled_grapheme in self.styled_graphemes.iter() {
-            write!(f, "{}", styled_grapheme.style.apply(styled_grapheme.ch))?;
+            if styled_grapheme.link() != link {
+                link = styled_grapheme.link();
+                write_hyperlink(f, link)?;
+            }
+            write!(
+                f,
+                "{}",
+                styled_grapheme.style.apply(&styled_grapheme.cluster)
+            )?;
+        }
+        if link.is_some() {
+            write_hyperlink(f, None)?;
         }
         Ok(())
     }
 }
 
+/// Writes the OSC 8 sequence starting a hyperlink to the target,
+/// or ending the current hyperlink with `None`.
+/// Terminals without support for hyperlinks ignore it.
+fn write_hyperlink<W: fmt::Write>(w: &mut W, target: Option<&str>) -> fmt::Result {
+    write!(w, "\x1b]8;;{}\x1b\\", target.unwrap_or_default())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -341,12 +596,33 @@ mod test {
         use super::*;
 
         #[test]
+        #[allow(clippy::clone_on_copy)]
         fn test() {
             let style = ContentStyle::default();
             let graphemes = StyledGraphemes::from_str("abc", style.clone());
             assert_eq!(3, graphemes.0.len());
             assert!(graphemes.0.iter().all(|g| g.style == style));
         }
+
+        #[test]
+        fn test_with_clusters() {
+            let graphemes = StyledGraphemes::from("e\u{301}👨\u{200d}👩\u{200d}👧🇯🇵");
+            assert_eq!(
+                vec!["e\u{301}", "👨\u{200d}👩\u{200d}👧", "🇯🇵"],
+                graphemes.iter().map(|g| g.as_str()).collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                vec![1, 2, 2],
+                graphemes.iter().map(|g| g.width()).collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_crlf() {
+            let graphemes = StyledGraphemes::from("a\r\nb");
+            assert_eq!(3, graphemes.len());
+            assert_eq!(2, graphemes.widths());
+        }
     }
 
     mod chars {
@@ -370,6 +646,18 @@ mod test {
         }
     }
 
+    mod truncate {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("abc", StyledGraphemes::from("abc").truncate(3).to_string());
+            assert_eq!("ab…", StyledGraphemes::from("abcd").truncate(3).to_string());
+            assert_eq!("a…", StyledGraphemes::from("a日本").truncate(3).to_string());
+            assert_eq!("", StyledGraphemes::from("abc").truncate(0).to_string());
+        }
+    }
+
     mod replace_char {
         use super::*;
 
@@ -415,6 +703,7 @@ mod test {
         use super::*;
 
         #[test]
+        #[allow(clippy::clone_on_copy)]
         fn test() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
@@ -429,6 +718,7 @@ mod test {
         use super::*;
 
         #[test]
+        #[allow(clippy::clone_on_copy)]
         fn test_apply_style_at_specific_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
@@ -439,6 +729,7 @@ mod test {
         }
 
         #[test]
+        #[allow(clippy::clone_on_copy)]
         fn test_apply_style_at_out_of_bounds_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
@@ -556,9 +847,47 @@ mod test {
             let display = graphemes.styled_display();
             assert_eq!(format!("{}", display), "abc"); // Assuming default styles do not alter appearance
         }
+
+        #[test]
+        fn test_with_hyperlink() {
+            let mut graphemes = StyledGraphemes::from("a");
+            graphemes.append(&mut StyledGraphemes::from("bc").hyperlink("https://example.com\x07"));
+            assert_eq!(3, graphemes.widths());
+            assert_eq!(
+                format!("{}", graphemes.styled_display()),
+                "a\x1b]8;;https://example.com\x1b\\bc\x1b]8;;\x1b\\"
+            );
+        }
     }
 
     #[cfg(test)]
+    mod locate {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let input = StyledGraphemes::from("1234567890");
+            assert_eq!((0, 0), input.locate(4, 0));
+            assert_eq!((1, 1), input.locate(4, 5));
+            assert_eq!((2, 1), input.locate(4, 9));
+            assert_eq!((2, 2), input.locate(4, 10));
+        }
+
+        #[test]
+        fn test_with_clusters() {
+            let input = StyledGraphemes::from("a👍🏽e\u{301}b");
+            assert_eq!((0, 3), input.locate(4, 2));
+            assert_eq!((1, 0), input.locate(4, 3));
+        }
+
+        #[test]
+        fn test_with_wide_characters() {
+            let input = StyledGraphemes::from("aあい");
+            assert_eq!((0, 1), input.locate(4, 1));
+            assert_eq!((1, 0), input.locate(4, 2));
+        }
+    }
+
     mod matrixify {
         use super::*;
 
@@ -621,4 +950,56 @@ mod test {
             assert_eq!(offset, 0);
         }
     }
+
+    mod matrixify_with {
+        use super::*;
+
+        fn rows(input: &str, width: usize, indent: usize) -> Vec<String> {
+            StyledGraphemes::from(input)
+                .matrixify_with(width, 100, 0, Wrap::Word { indent })
+                .0
+                .iter()
+                .map(|row| row.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                vec!["the quick", "brown fox", "jumps"],
+                rows("the quick brown fox jumps", 10, 0)
+            );
+            assert_eq!(vec!["hello", "world"], rows("hello  world", 5, 0));
+        }
+
+        #[test]
+        fn test_with_leading_whitespace() {
+            assert_eq!(
+                vec!["leading", "spaces", "here"],
+                rows("  leading spaces here", 8, 0)
+            );
+            assert_eq!(vec!["  ab", "cd"], rows("  ab cd", 4, 0));
+            assert_eq!(vec!["ab"], rows("  ab", 2, 0));
+        }
+
+        #[test]
+        fn test_with_indent() {
+            assert_eq!(
+                vec!["the quick", "  brown", "  fox", "  jumps"],
+                rows("the quick brown fox jumps", 9, 2)
+            );
+        }
+
+        #[test]
+        fn test_with_long_word() {
+            assert_eq!(vec!["a", "abcdefgh", "ij b"], rows("a abcdefghij b", 8, 0));
+            assert_eq!(vec!["abcd", "  ef", "  gh"], rows("abcdefgh", 4, 2));
+        }
+
+        #[test]
+        fn test_with_cjk() {
+            // Lines may break between ideographs without whitespace.
+            assert_eq!(vec!["日本語の", "文章"], rows("日本語の文章", 8, 0));
+        }
+    }
 }
@@ -0,0 +1,380 @@
+//! Conversion between strings with ANSI escape sequences and `StyledGraphemes`.
+//!
+//! Output of other tools (e.g. `git`, `ls --color` or compilers) colors text
+//! with SGR (Select Graphic Rendition) sequences such as `\x1b[1;31m`.
+//! `StyledGraphemes::from_ansi` turns these sequences into the style of each
+//! grapheme, so that they are neither counted as printable characters
+//! nor matched when filtering, and `StyledGraphemes::to_ansi` writes them back.
+//! Hyperlinks of OSC 8 sequences are kept as well.
+use crate::crossterm::style::{Attribute, Color, ContentStyle};
+
+use super::{write_hyperlink, StyledGraphemes};
+
+/// The colors of the SGR codes 30-37 (and 40-47 for the background).
+const COLORS: [Color; 8] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+];
+
+/// The colors of the SGR codes 90-97 (and 100-107 for the background).
+const BRIGHT_COLORS: [Color; 8] = [
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// The attributes for the kinds of underline, which exclude each other.
+const UNDERLINES: [Attribute; 5] = [
+    Attribute::Underlined,
+    Attribute::DoubleUnderlined,
+    Attribute::Undercurled,
+    Attribute::Underdotted,
+    Attribute::Underdashed,
+];
+
+impl StyledGraphemes {
+    /// Parses a string with ANSI escape sequences into styled graphemes.
+    ///
+    /// SGR sequences (`ESC [ ... m`) set the style of the following graphemes,
+    /// including 8, 16, 256 and 24-bit colors, and OSC 8 sequences their hyperlink.
+    /// Other escape sequences, such as cursor movements, are dropped.
+    pub fn from_ansi<S: AsRef<str>>(string: S) -> Self {
+        let mut ret = StyledGraphemes::default();
+        let mut style = ContentStyle::default();
+        let mut link = None;
+        let mut text = String::new();
+        let styled = |text: &str, style: ContentStyle, link: &Option<String>| {
+            let graphemes = StyledGraphemes::from_str(text, style);
+            match link {
+                Some(link) => graphemes.hyperlink(link),
+                None => graphemes,
+            }
+        };
+
+        let mut chars = string.as_ref().chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch != '\x1b' {
+                text.push(ch);
+                continue;
+            }
+            ret.append(&mut styled(&text, style, &link));
+            text.clear();
+
+            match chars.next() {
+                // CSI: parameter and intermediate bytes, up to the final byte.
+                Some('[') => {
+                    let mut params = String::new();
+                    for ch in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&ch) {
+                            // Private sequences (e.g. `ESC [ > 4 m`) are not SGR.
+                            let private = params.starts_with(['<', '=', '>', '?']);
+                            if ch == 'm' && !private {
+                                apply_sgr(&mut style, &params);
+                            }
+                            break;
+                        }
+                        params.push(ch);
+                    }
+                }
+                // OSC: terminated by BEL or ST (`ESC \`).
+                Some(']') => {
+                    let mut payload = String::new();
+                    while let Some(ch) = chars.next() {
+                        if ch == '\x07' {
+                            break;
+                        }
+                        if ch == '\x1b' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                        payload.push(ch);
+                    }
+                    // OSC 8 ; params ; target, where an empty target ends the hyperlink.
+                    if let Some((_, target)) = payload
+                        .strip_prefix("8;")
+                        .and_then(|rest| rest.split_once(';'))
+                    {
+                        link = (!target.is_empty()).then(|| target.to_string());
+                    }
+                }
+                // Other sequences end with the first character after ESC
+                // and its intermediate bytes (e.g. `ESC ( B`).
+                Some(' '..='/') => {
+                    while chars.next_if(|ch| (' '..='/').contains(ch)).is_some() {}
+                    chars.next();
+                }
+                _ => (),
+            }
+        }
+        ret.append(&mut styled(&text, style, &link));
+        ret
+    }
+
+    /// Returns the graphemes as a string with ANSI escape sequences for their styles,
+    /// which can be parsed back with `StyledGraphemes::from_ansi`.
+    ///
+    /// Consecutive graphemes with the same style share a single sequence,
+    /// and each styled run is followed by sequences resetting its style.
+    pub fn to_ansi(&self) -> String {
+        let mut ret = String::new();
+        let mut link = None;
+        let mut graphemes = self.iter().peekable();
+        while let Some(first) = graphemes.next() {
+            if first.link() != link {
+                link = first.link();
+                // Writing to a `String` does not fail.
+                let _ = write_hyperlink(&mut ret, link);
+            }
+            let mut run = first.as_str().to_string();
+            while let Some(next) =
+                graphemes.next_if(|g| g.style == first.style && g.link() == first.link())
+            {
+                run.push_str(next.as_str());
+            }
+            if first.style == ContentStyle::default() {
+                ret.push_str(&run);
+            } else {
+                ret.push_str(&first.style.apply(run).to_string());
+            }
+        }
+        if link.is_some() {
+            let _ = write_hyperlink(&mut ret, None);
+        }
+        ret
+    }
+}
+
+/// Applies the parameters of an SGR sequence, e.g. `1;38;5;208`, to the style.
+fn apply_sgr(style: &mut ContentStyle, params: &str) {
+    // Parameters are separated by `;`, and may have sub-parameters separated by `:`.
+    // Empty parameters are 0, so `ESC [ m` resets the style.
+    let params = params
+        .split(';')
+        .map(|param| {
+            param
+                .split(':')
+                .map(|value| value.parse::<u16>().unwrap_or(0))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut i = 0;
+    while i < params.len() {
+        let param = &params[i];
+        let attributes = &mut style.attributes;
+        match param[0] {
+            0 => *style = ContentStyle::default(),
+            1 => attributes.set(Attribute::Bold),
+            2 => attributes.set(Attribute::Dim),
+            3 => attributes.set(Attribute::Italic),
+            4 => {
+                for underline in UNDERLINES {
+                    attributes.unset(underline);
+                }
+                match param.get(1) {
+                    Some(0) => (),
+                    Some(2) => attributes.set(Attribute::DoubleUnderlined),
+                    Some(3) => attributes.set(Attribute::Undercurled),
+                    Some(4) => attributes.set(Attribute::Underdotted),
+                    Some(5) => attributes.set(Attribute::Underdashed),
+                    _ => attributes.set(Attribute::Underlined),
+                }
+            }
+            5 => attributes.set(Attribute::SlowBlink),
+            6 => attributes.set(Attribute::RapidBlink),
+            7 => attributes.set(Attribute::Reverse),
+            8 => attributes.set(Attribute::Hidden),
+            9 => attributes.set(Attribute::CrossedOut),
+            21 => {
+                for underline in UNDERLINES {
+                    attributes.unset(underline);
+                }
+                attributes.set(Attribute::DoubleUnderlined);
+            }
+            22 => {
+                attributes.unset(Attribute::Bold);
+                attributes.unset(Attribute::Dim);
+            }
+            23 => attributes.unset(Attribute::Italic),
+            24 => {
+                for underline in UNDERLINES {
+                    attributes.unset(underline);
+                }
+            }
+            25 => {
+                attributes.unset(Attribute::SlowBlink);
+                attributes.unset(Attribute::RapidBlink);
+            }
+            27 => attributes.unset(Attribute::Reverse),
+            28 => attributes.unset(Attribute::Hidden),
+            29 => attributes.unset(Attribute::CrossedOut),
+            53 => attributes.set(Attribute::OverLined),
+            55 => attributes.unset(Attribute::OverLined),
+            code @ 30..=37 => style.foreground_color = Some(COLORS[code as usize - 30]),
+            code @ 40..=47 => style.background_color = Some(COLORS[code as usize - 40]),
+            code @ 90..=97 => style.foreground_color = Some(BRIGHT_COLORS[code as usize - 90]),
+            code @ 100..=107 => style.background_color = Some(BRIGHT_COLORS[code as usize - 100]),
+            39 => style.foreground_color = None,
+            49 => style.background_color = None,
+            59 => style.underline_color = None,
+            code @ (38 | 48 | 58) => {
+                let (color, used) = extended_color(param, &params[i + 1..]);
+                i += used;
+                match code {
+                    38 => style.foreground_color = color.or(style.foreground_color),
+                    48 => style.background_color = color.or(style.background_color),
+                    _ => style.underline_color = color.or(style.underline_color),
+                }
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+}
+
+/// Parses the color of an extended color parameter (38, 48 or 58),
+/// given either as sub-parameters (`38:5:n`, `38:2::r:g:b`)
+/// or as the following parameters (`38;5;n`, `38;2;r;g;b`).
+///
+/// Returns the color, if valid, and the number of following parameters consumed.
+fn extended_color(param: &[u16], rest: &[Vec<u16>]) -> (Option<Color>, usize) {
+    let (values, used) = if param.len() > 1 {
+        (param[1..].to_vec(), 0)
+    } else {
+        let values = rest.iter().map(|param| param[0]).collect::<Vec<_>>();
+        let used = match values.first() {
+            Some(5) => 2,
+            Some(2) => 4,
+            _ => 0,
+        };
+        (values, used.min(rest.len()))
+    };
+
+    let color = match values.as_slice() {
+        // The first 16 colors of the palette are the named ones,
+        // which crossterm also writes as `38;5;n`.
+        [5, n @ 0..=7, ..] => Some(COLORS[*n as usize]),
+        [5, n @ 8..=15, ..] => Some(BRIGHT_COLORS[*n as usize - 8]),
+        [5, n, ..] => u8::try_from(*n).ok().map(Color::AnsiValue),
+        // The color space id may precede the components in the sub-parameter form.
+        [2, _, r, g, b] if param.len() > 1 => rgb(*r, *g, *b),
+        [2, r, g, b, ..] => rgb(*r, *g, *b),
+        _ => None,
+    };
+    (color, used)
+}
+
+fn rgb(r: u16, g: u16, b: u16) -> Option<Color> {
+    Some(Color::Rgb {
+        r: u8::try_from(r).ok()?,
+        g: u8::try_from(g).ok()?,
+        b: u8::try_from(b).ok()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    mod from_ansi {
+        use crate::{
+            crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+            grapheme::StyledGraphemes,
+            style::StyleBuilder,
+        };
+
+        #[test]
+        fn test() {
+            let graphemes = StyledGraphemes::from_ansi("\x1b[1;31mab\x1b[0mc\x1b[32md\x1b[m");
+            assert_eq!("abcd", graphemes.to_string());
+            let red = StyleBuilder::new()
+                .fgc(Color::DarkRed)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            assert_eq!(
+                vec![
+                    red,
+                    red,
+                    ContentStyle::default(),
+                    StyleBuilder::new().fgc(Color::DarkGreen).build(),
+                ],
+                graphemes.iter().map(|g| g.style).collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_extended_colors() {
+            let graphemes = StyledGraphemes::from_ansi(
+                "\x1b[38;5;208;48;2;1;2;3ma\x1b[38:2::4:5:6;39mb\x1b[4:3;58:5:1mc",
+            );
+            assert_eq!(
+                Some(Color::AnsiValue(208)),
+                graphemes[0].style.foreground_color
+            );
+            assert_eq!(
+                Some(Color::Rgb { r: 1, g: 2, b: 3 }),
+                graphemes[0].style.background_color
+            );
+            assert_eq!(None, graphemes[1].style.foreground_color);
+            assert_eq!(
+                Some(Color::Rgb { r: 1, g: 2, b: 3 }),
+                graphemes[1].style.background_color
+            );
+            assert!(graphemes[2].style.attributes.has(Attribute::Undercurled));
+            assert_eq!(Some(Color::DarkRed), graphemes[2].style.underline_color);
+        }
+
+        #[test]
+        fn test_with_other_sequences() {
+            let graphemes = StyledGraphemes::from_ansi(
+                "\x1b[2K\x1b]0;title\x07a\x1b]1;icon\x1b\\b\x1b[>4;2mc\x1b(B",
+            );
+            assert_eq!("abc", graphemes.to_string());
+            assert!(graphemes.iter().all(|g| g.style == ContentStyle::default()));
+        }
+    }
+
+    mod hyperlink {
+        use crate::grapheme::StyledGraphemes;
+
+        #[test]
+        fn test() {
+            let graphemes = StyledGraphemes::from_ansi(
+                "a\x1b]8;id=1;https://example.com\x1b\\b\x1b[1mc\x1b]8;;\x07d",
+            );
+            assert_eq!("abcd", graphemes.to_string());
+            assert_eq!(
+                vec![
+                    None,
+                    Some("https://example.com"),
+                    Some("https://example.com"),
+                    None
+                ],
+                graphemes.iter().map(|g| g.link()).collect::<Vec<_>>(),
+            );
+            assert_eq!(graphemes, StyledGraphemes::from_ansi(graphemes.to_ansi()));
+        }
+    }
+
+    mod to_ansi {
+        use crate::{crossterm::style::Color, grapheme::StyledGraphemes, style::StyleBuilder};
+
+        #[test]
+        fn test() {
+            let mut graphemes =
+                StyledGraphemes::from_str("ab", StyleBuilder::new().fgc(Color::Red).build());
+            graphemes.append(&mut StyledGraphemes::from("c"));
+            assert_eq!("\x1b[38;5;9mab\x1b[39mc", graphemes.to_ansi());
+            assert_eq!(graphemes, StyledGraphemes::from_ansi(graphemes.to_ansi()));
+        }
+    }
+}
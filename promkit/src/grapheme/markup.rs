@@ -0,0 +1,242 @@
+//! A small markup language for styled text, e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`,
+//! so that text with several styles can be written without assembling
+//! `StyledGraphemes` by hand.
+use crate::crossterm::style::{Attribute, Color, ContentStyle};
+
+use super::StyledGraphemes;
+
+impl StyledGraphemes {
+    /// Parses a string with markup tags into styled graphemes.
+    ///
+    /// Tags in square brackets style the text up to the matching `[/]`.
+    /// A tag consists of one or more space-separated items:
+    ///
+    /// - attributes: `bold`, `dim`, `italic`, `underline`, `blink`, `reverse`, `hidden`, `strike`
+    /// - colors: `fg=<color>` and `bg=<color>`, where a color is one of the names
+    ///   of crossterm (e.g. `red`, `dark_grey`), a 256-color index (e.g. `208`)
+    ///   or a hex RGB value (e.g. `#ff8700`)
+    /// - hyperlinks: `link=<target>`, e.g. `link=https://example.com`
+    ///
+    /// Tags can be nested, and the styles of the inner tags are applied on top of the outer ones.
+    /// Brackets that do not form a valid tag are kept as they are, e.g. `[1, 2]`,
+    /// and `[[` is a literal `[`.
+    pub fn from_markup<S: AsRef<str>>(string: S) -> Self {
+        let mut ret = StyledGraphemes::default();
+        let mut tags: Vec<Style> = vec![];
+        let mut text = String::new();
+
+        let mut rest = string.as_ref();
+        while let Some(start) = rest.find('[') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(escaped) = rest.strip_prefix("[[") {
+                text.push('[');
+                rest = escaped;
+                continue;
+            }
+
+            let current = tags.last().cloned().unwrap_or_default();
+            let tag = rest[1..].find(']').map(|end| &rest[1..end + 1]);
+            match tag.and_then(|tag| Some((tag, parse_tag(tag, current.clone())?))) {
+                // A closing tag without an opening one is kept as it is.
+                Some((_, Tag::Close)) if tags.is_empty() => {
+                    text.push('[');
+                    rest = &rest[1..];
+                }
+                Some((tag, parsed)) => {
+                    ret.append(&mut current.apply(&text));
+                    text.clear();
+                    match parsed {
+                        Tag::Open(style) => tags.push(style),
+                        Tag::Close => {
+                            tags.pop();
+                        }
+                    }
+                    rest = &rest[tag.len() + 2..];
+                }
+                None => {
+                    text.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        ret.append(&mut tags.last().cloned().unwrap_or_default().apply(&text));
+        ret
+    }
+
+    /// Escapes a string so that `StyledGraphemes::from_markup` returns it as it is.
+    pub fn escape_markup<S: AsRef<str>>(string: S) -> String {
+        string.as_ref().replace('[', "[[")
+    }
+}
+
+/// The style and the hyperlink of a tag.
+#[derive(Clone, Default)]
+struct Style {
+    style: ContentStyle,
+    link: Option<String>,
+}
+
+impl Style {
+    fn apply(&self, text: &str) -> StyledGraphemes {
+        let graphemes = StyledGraphemes::from_str(text, self.style);
+        match &self.link {
+            Some(link) => graphemes.hyperlink(link),
+            None => graphemes,
+        }
+    }
+}
+
+enum Tag {
+    /// Starts the style, which includes the style of the enclosing tags.
+    Open(Style),
+    /// Ends the style of the innermost tag.
+    Close,
+}
+
+/// Parses the content of a tag, e.g. `bold fg=red`, on top of the given style.
+/// Returns `None` if it is not a valid tag.
+fn parse_tag(tag: &str, mut current: Style) -> Option<Tag> {
+    if tag == "/" {
+        return Some(Tag::Close);
+    }
+
+    let style = &mut current.style;
+    for item in tag.split(' ') {
+        match item {
+            "bold" => style.attributes.set(Attribute::Bold),
+            "dim" => style.attributes.set(Attribute::Dim),
+            "italic" => style.attributes.set(Attribute::Italic),
+            "underline" => style.attributes.set(Attribute::Underlined),
+            "blink" => style.attributes.set(Attribute::SlowBlink),
+            "reverse" => style.attributes.set(Attribute::Reverse),
+            "hidden" => style.attributes.set(Attribute::Hidden),
+            "strike" => style.attributes.set(Attribute::CrossedOut),
+            _ => {
+                if let Some(color) = item.strip_prefix("fg=") {
+                    style.foreground_color = Some(parse_color(color)?);
+                } else if let Some(color) = item.strip_prefix("bg=") {
+                    style.background_color = Some(parse_color(color)?);
+                } else if let Some(link) = item.strip_prefix("link=") {
+                    if link.is_empty() {
+                        return None;
+                    }
+                    current.link = Some(link.to_string());
+                } else {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(Tag::Open(current))
+}
+
+fn parse_color(color: &str) -> Option<Color> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb {
+            r: component(0)?,
+            g: component(2)?,
+            b: component(4)?,
+        });
+    }
+    if let Ok(value) = color.parse::<u8>() {
+        return Some(Color::AnsiValue(value));
+    }
+    Color::try_from(color).ok()
+}
+
+#[cfg(test)]
+mod test {
+    mod from_markup {
+        use crate::{
+            crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+            grapheme::StyledGraphemes,
+            style::StyleBuilder,
+        };
+
+        fn styles(graphemes: &StyledGraphemes) -> Vec<ContentStyle> {
+            graphemes.iter().map(|g| g.style).collect()
+        }
+
+        #[test]
+        fn test() {
+            let graphemes = StyledGraphemes::from_markup("[bold]a[/]b[fg=red]c[/]");
+            assert_eq!("abc", graphemes.to_string());
+            assert_eq!(
+                vec![
+                    StyleBuilder::new()
+                        .attrs(Attributes::from(Attribute::Bold))
+                        .build(),
+                    ContentStyle::default(),
+                    StyleBuilder::new().fgc(Color::Red).build(),
+                ],
+                styles(&graphemes),
+            );
+        }
+
+        #[test]
+        fn test_with_nested_tags() {
+            let graphemes =
+                StyledGraphemes::from_markup("[fg=#ff8700 bg=236]a[italic fg=dark_cyan]b[/]c");
+            assert_eq!("abc", graphemes.to_string());
+            let outer = StyleBuilder::new()
+                .fgc(Color::Rgb {
+                    r: 255,
+                    g: 135,
+                    b: 0,
+                })
+                .bgc(Color::AnsiValue(236))
+                .build();
+            let inner = StyleBuilder::new()
+                .fgc(Color::DarkCyan)
+                .bgc(Color::AnsiValue(236))
+                .attrs(Attributes::from(Attribute::Italic))
+                .build();
+            assert_eq!(vec![outer, inner, outer], styles(&graphemes));
+        }
+
+        #[test]
+        fn test_with_hyperlink() {
+            let graphemes = StyledGraphemes::from_markup(
+                "see [link=https://example.com/pr/1 fg=blue]#1[/] and [link=]x[/]",
+            );
+            assert_eq!("see #1 and [link=]x[/]", graphemes.to_string());
+            let links = graphemes.iter().map(|g| g.link()).collect::<Vec<_>>();
+            assert_eq!(None, links[3]);
+            assert_eq!(Some("https://example.com/pr/1"), links[4]);
+            assert_eq!(Some("https://example.com/pr/1"), links[5]);
+            assert!(links[6..].iter().all(Option::is_none));
+        }
+
+        #[test]
+        fn test_with_literal_brackets() {
+            for literal in [
+                "[1, 2]",
+                "[]",
+                "[/]",
+                "a [fg=nope] b",
+                "[bold",
+                "#[derive(Clone)]",
+            ] {
+                let graphemes = StyledGraphemes::from_markup(literal);
+                assert_eq!(literal, graphemes.to_string());
+                assert!(styles(&graphemes)
+                    .iter()
+                    .all(|style| *style == ContentStyle::default()));
+            }
+            assert_eq!(
+                "[bold]",
+                StyledGraphemes::from_markup("[[bold]").to_string()
+            );
+            assert_eq!(
+                "[bold]",
+                StyledGraphemes::from_markup(StyledGraphemes::escape_markup("[bold]")).to_string()
+            );
+        }
+    }
+}
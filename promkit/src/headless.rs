@@ -0,0 +1,384 @@
+//! A virtual terminal for driving prompts without a TTY.
+//!
+//! `Headless` is a [`Backend`] with a fixed viewport and a scripted list of events
+//! instead of a real terminal. `Headless::run` runs a `Prompt` through `Prompt::run`
+//! on it, so that custom renderers and the built-in presets can be tested
+//! in ordinary unit tests, including how the prompt is drawn and finished.
+//!
+//! ```ignore
+//! use promkit::{
+//!     crossterm::event::{KeyCode, KeyModifiers},
+//!     headless::Headless,
+//!     preset::readline::Readline,
+//! };
+//!
+//! let mut p = Readline::default().prompt()?;
+//! let output = Headless::new(20, 5)
+//!     .text("hello")
+//!     .key(KeyCode::Enter, KeyModifiers::NONE)
+//!     .run(&mut p)?;
+//! assert_eq!(Some(String::from("hello")), output.ret);
+//! ```
+use std::{
+    collections::VecDeque,
+    error, fmt, io, mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+use crate::{
+    backend::Backend,
+    crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
+    grapheme::StyledGraphemes,
+    pane::Caret,
+    terminal::Terminal,
+    Error, Prompt, Renderer,
+};
+
+/// The result of running a prompt on a `Headless` terminal.
+pub struct Output<R> {
+    /// The result produced by `Finalizer::finalize`,
+    /// or `None` if the events ran out before the renderer quit.
+    pub ret: Option<R>,
+    /// The rows of the screen as they were drawn last,
+    /// i.e. the summary if the prompt collapsed into one when it finished.
+    pub screen: Vec<StyledGraphemes>,
+    /// The position of the terminal cursor on the screen, if any.
+    pub caret: Option<Caret>,
+}
+
+/// A single step of the script.
+enum Step {
+    /// An event passed to `Renderer::evaluate`.
+    Event(Event),
+    /// A tick passed to `Renderer::on_tick`.
+    Tick,
+}
+
+/// The error returned by `Headless` when the script has run out,
+/// which ends `Prompt::run` without a result.
+#[derive(Debug)]
+struct ScriptEnded;
+
+impl fmt::Display for ScriptEnded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the script of the headless terminal has run out")
+    }
+}
+
+impl error::Error for ScriptEnded {}
+
+/// A virtual terminal with a fixed size and a scripted list of events.
+pub struct Headless {
+    /// The size of the virtual terminal in characters, as `(columns, rows)`.
+    size: Mutex<(u16, u16)>,
+    /// Events and ticks to be delivered to the renderer in order.
+    steps: Mutex<VecDeque<Step>>,
+    /// Whether raw mode is enabled, i.e. a session is in progress.
+    raw_mode: AtomicBool,
+}
+
+impl Headless {
+    /// Creates a new `Headless` terminal with the specified size and no events.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: Mutex::new((width, height)),
+            steps: Mutex::new(VecDeque::new()),
+            raw_mode: AtomicBool::new(false),
+        }
+    }
+
+    /// Appends an event to the script.
+    pub fn event(mut self, event: Event) -> Self {
+        self.steps_mut().push_back(Step::Event(event));
+        self
+    }
+
+    /// Appends multiple events to the script.
+    pub fn events<I: IntoIterator<Item = Event>>(mut self, events: I) -> Self {
+        self.steps_mut().extend(events.into_iter().map(Step::Event));
+        self
+    }
+
+    /// Appends a tick to the script, as if the tick interval had elapsed.
+    pub fn tick(mut self) -> Self {
+        self.steps_mut().push_back(Step::Tick);
+        self
+    }
+
+    /// Appends a key press with the given modifiers to the script.
+    pub fn key(self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.event(Event::Key(KeyEvent::new(code, modifiers)))
+    }
+
+    /// Appends a click with the left button at the given position of the screen.
+    pub fn click(self, column: u16, row: u16) -> Self {
+        self.event(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }))
+    }
+
+    /// Appends a key press for each character of the text to the script,
+    /// as if the text was typed by the user.
+    pub fn text<T: AsRef<str>>(self, text: T) -> Self {
+        let events = text
+            .as_ref()
+            .chars()
+            .map(|ch| Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE)))
+            .collect::<Vec<_>>();
+        self.events(events)
+    }
+
+    fn steps_mut(&mut self) -> &mut VecDeque<Step> {
+        self.steps.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Renders the current state of the renderer as the rows of the screen.
+    pub fn render<T: Renderer>(&self, renderer: &T) -> Vec<StyledGraphemes> {
+        self.render_with_caret(renderer).0
+    }
+
+    /// Renders the current state of the renderer as the rows of the screen
+    /// and the position of the terminal cursor.
+    pub fn render_with_caret<T: Renderer>(
+        &self,
+        renderer: &T,
+    ) -> (Vec<StyledGraphemes>, Option<Caret>) {
+        let (width, height) = *self.size.lock().unwrap_or_else(PoisonError::into_inner);
+        Terminal::layout_with_caret(&renderer.create_panes(width, height), height)
+            .unwrap_or_else(|| (vec![StyledGraphemes::from("⚠️ Insufficient Space")], None))
+    }
+
+    /// Runs the prompt on this terminal with the scripted events through `Prompt::run`.
+    ///
+    /// Events and ticks are passed to the renderer one by one until the renderer
+    /// signals `PromptSignal::Quit` or the script runs out.
+    /// `Event::Resize` changes the size of the virtual terminal.
+    /// Ticks are delivered only where they are scripted, whatever the tick interval
+    /// of the prompt is. The prompt area starts at the top of the screen,
+    /// and nothing is written to the writer of the prompt.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `Output`,
+    /// or the error returned by the prompt.
+    pub fn run<T: Renderer>(self, prompt: &mut Prompt<T>) -> crate::Result<Output<T::Return>> {
+        let mut terminal = Terminal::new(io::sink());
+        terminal.set_backend(Arc::new(self));
+        terminal.set_fullscreen(prompt.terminal.is_fullscreen());
+        terminal.set_mouse_capture(prompt.terminal.is_mouse_capture());
+
+        let terminal = mem::replace(&mut prompt.terminal, terminal);
+        // The interval never elapses, so that a tick is delivered
+        // only when `poll` times out at a scripted tick.
+        let tick_interval = prompt.tick_interval.replace(Duration::MAX);
+        let ret = prompt.run();
+        prompt.tick_interval = tick_interval;
+        let terminal = mem::replace(&mut prompt.terminal, terminal);
+
+        let ret = match ret {
+            Ok(ret) => Some(ret),
+            Err(Error::Io(e)) if e.get_ref().is_some_and(|e| e.is::<ScriptEnded>()) => None,
+            Err(e) => return Err(e),
+        };
+        let (screen, caret) = terminal.screen();
+        Ok(Output {
+            ret,
+            screen: screen.to_vec(),
+            caret,
+        })
+    }
+
+    /// Takes the next step of the script, or fails if it has run out.
+    fn next_step(&self) -> io::Result<Step> {
+        self.steps
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+            .ok_or_else(|| io::Error::other(ScriptEnded))
+    }
+}
+
+impl Backend for Headless {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(*self.size.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn cursor_position(&self) -> io::Result<Option<(u16, u16)>> {
+        Ok(Some((0, 0)))
+    }
+
+    fn enable_raw_mode(&self) -> io::Result<()> {
+        self.raw_mode.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn disable_raw_mode(&self) -> io::Result<()> {
+        self.raw_mode.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_raw_mode_enabled(&self) -> io::Result<bool> {
+        Ok(self.raw_mode.load(Ordering::Relaxed))
+    }
+
+    /// Returns whether the next step is an event without waiting.
+    /// A tick is taken from the script and reported as the timeout elapsing.
+    fn poll(&self, _: Duration) -> io::Result<bool> {
+        let mut steps = self.steps.lock().unwrap_or_else(PoisonError::into_inner);
+        match steps.front() {
+            Some(Step::Event(_)) => Ok(true),
+            Some(Step::Tick) => {
+                steps.pop_front();
+                Ok(false)
+            }
+            None => Err(io::Error::other(ScriptEnded)),
+        }
+    }
+
+    /// Takes the next event from the script, skipping the ticks before it.
+    fn read(&self) -> io::Result<Event> {
+        loop {
+            if let Step::Event(event) = self.next_step()? {
+                if let Event::Resize(width, height) = event {
+                    *self.size.lock().unwrap_or_else(PoisonError::into_inner) = (width, height);
+                }
+                return Ok(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod run {
+        use crate::{
+            crossterm::event::{KeyCode, KeyModifiers},
+            preset::listbox::Listbox,
+            PromptSignal,
+        };
+
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let mut p = Listbox::new(["a", "b", "c"])
+                .title("pick")
+                .prompt()
+                .unwrap();
+            let output = Headless::new(10, 10)
+                .key(KeyCode::Down, KeyModifiers::NONE)
+                .key(KeyCode::Enter, KeyModifiers::NONE)
+                .run(&mut p)
+                .unwrap();
+            assert_eq!(Some(String::from("b")), output.ret);
+            assert_eq!(
+                vec!["pick", "❯ b", "  c"],
+                output
+                    .screen
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_tick() {
+            struct Counter(usize);
+
+            impl crate::Finalizer for Counter {
+                type Return = usize;
+
+                fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+                    Ok(self.0)
+                }
+            }
+
+            impl Renderer for Counter {
+                fn create_panes(&self, _: u16, _: u16) -> Vec<crate::pane::Pane> {
+                    vec![crate::pane::Pane::new(
+                        vec![StyledGraphemes::from(self.0.to_string())],
+                        0,
+                    )]
+                }
+
+                fn evaluate(&mut self, _: &Event) -> anyhow::Result<PromptSignal> {
+                    Ok(PromptSignal::Quit)
+                }
+
+                fn on_tick(&mut self) -> anyhow::Result<PromptSignal> {
+                    self.0 += 1;
+                    Ok(PromptSignal::Continue)
+                }
+            }
+
+            let output = Headless::new(10, 2)
+                .tick()
+                .tick()
+                .key(KeyCode::Enter, KeyModifiers::NONE)
+                .run(&mut Prompt::new(Counter(0)))
+                .unwrap();
+            assert_eq!(Some(2), output.ret);
+            assert_eq!(vec![StyledGraphemes::from("2")], output.screen);
+        }
+
+        #[test]
+        fn test_without_quit() {
+            let mut p = Listbox::new(["a", "b", "c"]).prompt().unwrap();
+            let output = Headless::new(10, 2)
+                .key(KeyCode::Down, KeyModifiers::NONE)
+                .run(&mut p)
+                .unwrap();
+            assert_eq!(None, output.ret);
+            assert_eq!(
+                vec!["❯ b", "  c"],
+                output
+                    .screen
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_resize() {
+            let mut p = Listbox::new(["a", "b", "c"]).prompt().unwrap();
+            let output = Headless::new(10, 5)
+                .event(Event::Resize(10, 2))
+                .run(&mut p)
+                .unwrap();
+            assert_eq!(
+                vec!["❯ a", "  b"],
+                output
+                    .screen
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_answer() {
+            let mut p = Listbox::new(["a", "b", "c"])
+                .prompt()
+                .unwrap()
+                .id("pick")
+                .answers(crate::answers::Answers::default().with("pick", "c"));
+            let output = Headless::new(10, 5).run(&mut p).unwrap();
+            assert_eq!(Some(String::from("c")), output.ret);
+            assert!(output.screen.is_empty());
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Help for key bindings, generated from the active keymap.
+//!
+//! Keymaps are plain functions, so their bindings are described separately
+//! as a list of `Binding`s, which is attached to the keymap in `ActiveKeySwitcher`
+//! with `ActiveKeySwitcher::describe`. `State` renders the bindings of the
+//! active keymap either as a compact footer (e.g. `↑↓ move  enter select  ? help`)
+//! or expanded into one binding per row, and follows the switcher when it
+//! changes modes.
+//!
+//! The presets toggle the expanded help with `?`, except those that edit text
+//! (`Readline`, `Password`, `Confirm`, `QuerySelector` and `Form`), where `?` is
+//! typed into the text and <kbd>F1</kbd> toggles the help instead.
+use crate::{
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::{StyledGraphemes, Wrap},
+    pane::Pane,
+    style::StyleBuilder,
+};
+
+/// A description of a key binding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Binding {
+    /// The keys, e.g. `ctrl+c` or `↑↓`.
+    pub keys: &'static str,
+    /// What the keys do, e.g. `cancel`.
+    pub action: &'static str,
+}
+
+impl Binding {
+    pub const fn new(keys: &'static str, action: &'static str) -> Self {
+        Self { keys, action }
+    }
+}
+
+/// The priority of the compact footer, which is dropped before titles.
+const FOOTER_PRIORITY: i32 = -2;
+/// The priority of the expanded help, which is kept before the main widget.
+const EXPANDED_PRIORITY: i32 = 1;
+
+/// Represents the state of the help for the key bindings.
+#[derive(Clone)]
+pub struct State {
+    /// The keys that toggle the expanded help, e.g. `?`.
+    pub toggle_keys: &'static str,
+    /// Whether the compact footer is shown while the help is not expanded.
+    pub footer: bool,
+    /// Whether all bindings are shown, one per row.
+    pub expanded: bool,
+
+    /// Style for the keys.
+    pub key_style: ContentStyle,
+    /// Style for the descriptions of the keys.
+    pub action_style: ContentStyle,
+}
+
+impl State {
+    /// Creates a new `State` toggled by the given keys, with the footer hidden.
+    pub fn new(toggle_keys: &'static str) -> Self {
+        Self {
+            toggle_keys,
+            footer: false,
+            expanded: false,
+            key_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold))
+                .build(),
+            action_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+        }
+    }
+
+    /// Expands or collapses the help.
+    pub fn toggle(&mut self) {
+        self.expanded = !self.expanded;
+    }
+
+    fn styled(&self, binding: &Binding) -> StyledGraphemes {
+        StyledGraphemes::from_iter([
+            StyledGraphemes::from_str(binding.keys, self.key_style),
+            StyledGraphemes::from_str(format!(" {}", binding.action), self.action_style),
+        ])
+    }
+
+    /// Creates a pane showing the given bindings, which is empty
+    /// if the help is neither expanded nor shown as a footer.
+    pub fn create_pane(&self, bindings: &[Binding], width: u16, height: u16) -> Pane {
+        if self.expanded {
+            let toggle = Binding::new(self.toggle_keys, "close help");
+            let all = bindings.iter().chain([&toggle]).collect::<Vec<_>>();
+            let keys_width = all
+                .iter()
+                .map(|binding| StyledGraphemes::from(binding.keys).widths())
+                .max()
+                .unwrap_or_default();
+            let rows = all
+                .iter()
+                .flat_map(|binding| {
+                    let padding = keys_width - StyledGraphemes::from(binding.keys).widths();
+                    StyledGraphemes::from_iter([
+                        StyledGraphemes::from_str(binding.keys, self.key_style),
+                        StyledGraphemes::from(" ".repeat(padding + 1)),
+                        StyledGraphemes::from_str(binding.action, self.action_style),
+                    ])
+                    // Wrapped descriptions are aligned with the first row.
+                    .matrixify_with(
+                        width as usize,
+                        height as usize,
+                        0,
+                        Wrap::Word {
+                            indent: keys_width + 1,
+                        },
+                    )
+                    .0
+                })
+                .collect();
+            return Pane::new(rows, 0).with_priority(EXPANDED_PRIORITY);
+        }
+
+        if !self.footer {
+            return Pane::new(vec![], 0);
+        }
+
+        let width = width as usize;
+        let mut hint = self.styled(&Binding::new(self.toggle_keys, "help"));
+        let mut row = StyledGraphemes::default();
+        for binding in bindings {
+            let mut styled = self.styled(binding);
+            if row.widths() + styled.widths() + 2 + hint.widths() > width {
+                break;
+            }
+            row.append(&mut styled);
+            row.append(&mut StyledGraphemes::from("  "));
+        }
+        if row.widths() + hint.widths() <= width {
+            row.append(&mut hint);
+        }
+        Pane::new(vec![row], 0).with_priority(FOOTER_PRIORITY)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod create_pane {
+        use super::super::*;
+
+        const BINDINGS: &[Binding] = &[
+            Binding::new("↑↓", "move"),
+            Binding::new("enter", "select"),
+            Binding::new("ctrl+c", "cancel"),
+        ];
+
+        fn render(state: &State, width: u16) -> Vec<String> {
+            state
+                .create_pane(BINDINGS, width, 10)
+                .extract(10)
+                .iter()
+                .map(|row| row.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            let mut state = State::new("?");
+            assert!(render(&state, 40).is_empty());
+
+            state.footer = true;
+            assert_eq!(vec!["↑↓ move  enter select  ? help"], render(&state, 30));
+            assert_eq!(vec!["↑↓ move  ? help"], render(&state, 20));
+        }
+
+        #[test]
+        fn test_with_expanded() {
+            let mut state = State::new("?");
+            state.toggle();
+            assert_eq!(
+                vec![
+                    "↑↓     move",
+                    "enter  select",
+                    "ctrl+c cancel",
+                    "?      close help"
+                ],
+                render(&state, 40),
+            );
+            assert_eq!(
+                vec![
+                    "↑↓     move",
+                    "enter  select",
+                    "ctrl+c cancel",
+                    "?      close",
+                    "       help"
+                ],
+                render(&state, 13),
+            );
+        }
+    }
+}
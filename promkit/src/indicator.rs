@@ -0,0 +1,229 @@
+//! Scroll position indicators for list-like panes.
+//!
+//! `listbox::State`, `checkbox::State`, `tree::State` and `jsonstream::State`
+//! show a window of their items starting at the cursor. An `Indicator`
+//! adds a vertical scrollbar on the right of the window and/or a footer
+//! such as `12/348`, both computed from the cursor position and the number of items.
+//!
+//! ```ignore
+//! Listbox::new(items)
+//!     .indicator(Indicator {
+//!         scrollbar: Some(Scrollbar::default()),
+//!         footer: Some(StyleBuilder::new().fgc(Color::DarkGrey).build()),
+//!     })
+//!     .prompt()?
+//! ```
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    pane::Pane,
+};
+
+/// The appearance of a vertical scrollbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scrollbar {
+    /// Character for the part of the scrollbar outside of the thumb.
+    pub track: char,
+    /// Character for the thumb, which represents the visible part of the items.
+    pub thumb: char,
+    /// Style applied to the scrollbar.
+    pub style: ContentStyle,
+}
+
+impl Default for Scrollbar {
+    fn default() -> Self {
+        Self {
+            track: '│',
+            thumb: '█',
+            style: ContentStyle::default(),
+        }
+    }
+}
+
+/// Indicators of where the cursor is within the items, all disabled by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Indicator {
+    /// The scrollbar drawn in the rightmost column, if any.
+    pub scrollbar: Option<Scrollbar>,
+    /// Style of the footer showing the cursor position and the number of items,
+    /// e.g. `12/348`, or `None` not to show the footer.
+    pub footer: Option<ContentStyle>,
+}
+
+impl Indicator {
+    /// Returns the width left for the items within the given width.
+    pub fn inner_width(&self, width: u16) -> u16 {
+        match self.scrollbar {
+            Some(_) => width.saturating_sub(1),
+            None => width,
+        }
+    }
+
+    /// Creates a pane from the rows of the items shown within the given size,
+    /// adding the scrollbar and the footer for the cursor at `position` in `len` items.
+    ///
+    /// The rows are expected to be created with the width returned by `inner_width`.
+    /// The scrollbar is drawn when the pane is extracted, so that it spans
+    /// the rows the pane is actually given rather than the rows it asked for.
+    pub fn create_pane(
+        &self,
+        mut rows: Vec<StyledGraphemes>,
+        width: u16,
+        height: u16,
+        position: usize,
+        len: usize,
+    ) -> Pane {
+        let mut pane = match self.scrollbar {
+            Some(scrollbar) => {
+                let height = match self.footer {
+                    Some(_) => (height as usize).saturating_sub(1).max(1),
+                    None => height as usize,
+                };
+                // Keep the track as tall as the window even when the rows
+                // after the cursor run out near the end of the items.
+                let track = height.min(len.max(rows.len()));
+                rows.truncate(track);
+                rows.resize(track, StyledGraphemes::default());
+                Pane::new(rows, 0).with_track(Track {
+                    scrollbar,
+                    width: self.inner_width(width) as usize,
+                    position,
+                    len,
+                })
+            }
+            None => Pane::new(rows, 0),
+        };
+
+        if let Some(style) = self.footer {
+            pane = pane.with_footer(StyledGraphemes::from_str(
+                format!("{}/{}", if len == 0 { 0 } else { position + 1 }, len),
+                style,
+            ));
+        }
+        pane
+    }
+}
+
+/// A scrollbar to be drawn on the rows extracted from a pane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Track {
+    scrollbar: Scrollbar,
+    /// The width of the rows, not counting the scrollbar.
+    width: usize,
+    position: usize,
+    len: usize,
+}
+
+impl Track {
+    /// Pads the rows to the width and appends the scrollbar,
+    /// whose height is the number of the rows.
+    pub(crate) fn draw(&self, rows: &mut [StyledGraphemes]) {
+        let (start, end) = thumb(rows.len(), self.position, self.len);
+        for (i, row) in rows.iter_mut().enumerate() {
+            let fill = self.width.saturating_sub(row.widths());
+            row.append(&mut StyledGraphemes::from(" ".repeat(fill)));
+            let ch = if start <= i && i < end {
+                self.scrollbar.thumb
+            } else {
+                self.scrollbar.track
+            };
+            row.push_back(StyledGrapheme::new(ch, self.scrollbar.style));
+        }
+    }
+}
+
+/// Returns the range of rows of the thumb in a scrollbar of the given height,
+/// where the window starts at `position`, i.e. the cursor, in `len` items.
+fn thumb(height: usize, position: usize, len: usize) -> (usize, usize) {
+    if height == 0 || len <= height {
+        return (0, height);
+    }
+    let size = (height * height / len).max(1);
+    let start = position * (height - size) / (len - 1);
+    (start, start + size)
+}
+
+#[cfg(test)]
+mod test {
+    mod thumb {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!((0, 4), thumb(4, 0, 3));
+            assert_eq!((0, 1), thumb(4, 0, 10));
+            assert_eq!((1, 2), thumb(4, 4, 10));
+            assert_eq!((3, 4), thumb(4, 9, 10));
+        }
+    }
+
+    mod create_pane {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let indicator = Indicator {
+                scrollbar: Some(Scrollbar {
+                    track: '|',
+                    thumb: '#',
+                    style: ContentStyle::default(),
+                }),
+                footer: Some(ContentStyle::default()),
+            };
+            let rows = vec![StyledGraphemes::from("a"), StyledGraphemes::from("bb")];
+            assert_eq!(
+                vec!["a  #", "bb |", "1/10"],
+                indicator
+                    .create_pane(rows, 4, 3, 0, 10)
+                    .extract(3)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_fewer_rows() {
+            let indicator = Indicator {
+                scrollbar: Some(Scrollbar {
+                    track: '|',
+                    thumb: '#',
+                    style: ContentStyle::default(),
+                }),
+                footer: Some(ContentStyle::default()),
+            };
+            let rows = vec![StyledGraphemes::from("a"), StyledGraphemes::from("b")];
+            let pane = indicator.create_pane(rows, 4, 4, 5, 10);
+            // Only 2 of the 3 rows asked for are given, so the track is 1 row.
+            assert_eq!(
+                vec!["a  #", "6/10"],
+                pane.extract(2)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_at_last_item() {
+            let indicator = Indicator {
+                scrollbar: Some(Scrollbar {
+                    track: '|',
+                    thumb: '#',
+                    style: ContentStyle::default(),
+                }),
+                footer: Some(ContentStyle::default()),
+            };
+            let rows = vec![StyledGraphemes::from("j")];
+            assert_eq!(
+                vec!["j  |", "   |", "   #", "10/10"],
+                indicator
+                    .create_pane(rows, 4, 4, 9, 10)
+                    .extract(4)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
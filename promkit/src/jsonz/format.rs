@@ -252,6 +252,7 @@ mod tests {
         use crate::jsonz::{create_rows, format};
 
         #[test]
+        #[allow(clippy::needless_borrow)]
         fn test() {
             let expected = r#"
 {
@@ -0,0 +1,256 @@
+//! Layout trees for arranging panes in rows and columns.
+//!
+//! `Terminal` stacks the panes returned by `Renderer::create_panes` vertically.
+//! A `Layout` splits the space given to it among its children instead,
+//! either top to bottom (`Layout::rows`) or side by side (`Layout::columns`),
+//! and combines their panes into a single pane. Since a `Layout` is itself
+//! a `PaneFactory`, layouts can be nested to build arbitrary grids.
+//!
+//! ```ignore
+//! fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+//!     vec![
+//!         self.title_state.create_pane(width, height),
+//!         Layout::columns()
+//!             .child(Constraint::Percentage(40), &self.listbox_state)
+//!             .child(Constraint::Fill, &self.preview_state)
+//!             .create_pane(width, height),
+//!     ]
+//! }
+//! ```
+use crate::{
+    grapheme::StyledGraphemes,
+    pane::{Caret, Pane},
+    PaneFactory,
+};
+
+/// The size of a child of a `Layout` along the direction of the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// A fixed number of rows or columns.
+    Length(u16),
+    /// A percentage of the space given to the layout.
+    Percentage(u16),
+    /// An equal share of the space left over by the other children.
+    Fill,
+}
+
+/// The direction in which a `Layout` arranges its children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Children are stacked from top to bottom.
+    Vertical,
+    /// Children are placed side by side from left to right.
+    Horizontal,
+}
+
+/// A node of a layout tree, splitting its space among its children.
+pub struct Layout<'a> {
+    /// The direction in which the children are arranged.
+    direction: Direction,
+    /// The children with their sizes.
+    children: Vec<(Constraint, Box<dyn PaneFactory + 'a>)>,
+}
+
+impl<'a> Layout<'a> {
+    /// Creates an empty layout arranging its children in the given direction.
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            children: vec![],
+        }
+    }
+
+    /// Creates an empty layout stacking its children from top to bottom.
+    pub fn rows() -> Self {
+        Self::new(Direction::Vertical)
+    }
+
+    /// Creates an empty layout placing its children side by side.
+    pub fn columns() -> Self {
+        Self::new(Direction::Horizontal)
+    }
+
+    /// Appends a child with the given size,
+    /// e.g. a component state, a reference to it, or another `Layout`.
+    pub fn child<F: PaneFactory + 'a>(mut self, constraint: Constraint, factory: F) -> Self {
+        self.children.push((constraint, Box::new(factory)));
+        self
+    }
+
+    /// Splits the given space among the children according to their constraints.
+    ///
+    /// Lengths and percentages are resolved first, in order, and are cut off
+    /// when the space runs out. The rest is shared equally among `Fill` children,
+    /// with the remainder going to the earlier ones.
+    fn split(&self, total: u16) -> Vec<u16> {
+        let mut remaining = total;
+        let mut sizes = self
+            .children
+            .iter()
+            .map(|(constraint, _)| {
+                let size = match constraint {
+                    Constraint::Length(length) => *length,
+                    Constraint::Percentage(percentage) => {
+                        (total as u32 * (*percentage).min(100) as u32 / 100) as u16
+                    }
+                    Constraint::Fill => return 0,
+                }
+                .min(remaining);
+                remaining -= size;
+                size
+            })
+            .collect::<Vec<_>>();
+
+        let fills = self
+            .children
+            .iter()
+            .filter(|(constraint, _)| *constraint == Constraint::Fill)
+            .count() as u16;
+        if let Some(share) = remaining.checked_div(fills) {
+            let mut extra = remaining % fills;
+            for (size, (constraint, _)) in sizes.iter_mut().zip(&self.children) {
+                if *constraint == Constraint::Fill {
+                    *size = share + if extra > 0 { 1 } else { 0 };
+                    extra = extra.saturating_sub(1);
+                }
+            }
+        }
+        sizes
+    }
+}
+
+impl PaneFactory for Layout<'_> {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let mut rows: Vec<StyledGraphemes> = vec![];
+        let mut caret = None;
+
+        match self.direction {
+            Direction::Vertical => {
+                for ((_, factory), size) in self.children.iter().zip(self.split(height)) {
+                    if size == 0 {
+                        continue;
+                    }
+                    let pane = factory.create_pane(width, size);
+                    if caret.is_none() {
+                        caret = pane.extract_caret(size as usize).map(|c| Caret {
+                            row: rows.len() + c.row,
+                            ..c
+                        });
+                    }
+                    rows.extend(pane.extract(size as usize));
+                }
+            }
+            Direction::Horizontal => {
+                let sizes = self.split(width);
+                let last = sizes.iter().rposition(|size| *size > 0);
+                let mut left = 0;
+                for (i, ((_, factory), size)) in self.children.iter().zip(sizes).enumerate() {
+                    if size == 0 {
+                        continue;
+                    }
+                    let pane = factory.create_pane(size, height);
+                    if caret.is_none() {
+                        caret = pane.extract_caret(height as usize).map(|c| Caret {
+                            column: left + c.column,
+                            ..c
+                        });
+                    }
+                    let columns = pane.extract(height as usize);
+                    if rows.len() < columns.len() {
+                        rows.resize(columns.len(), pad(StyledGraphemes::default(), left));
+                    }
+                    for (j, row) in rows.iter_mut().enumerate() {
+                        let mut column = columns.get(j).cloned().unwrap_or_default();
+                        // The last column is not padded to avoid trailing spaces.
+                        if Some(i) != last {
+                            column = pad(column, size as usize);
+                        }
+                        row.append(&mut column);
+                    }
+                    left += size as usize;
+                }
+            }
+        }
+
+        Pane::new(rows, 0).with_caret(caret)
+    }
+}
+
+/// Pads the row with spaces up to the given width.
+fn pad(mut row: StyledGraphemes, width: usize) -> StyledGraphemes {
+    let mut spaces = StyledGraphemes::from(" ".repeat(width.saturating_sub(row.widths())));
+    row.append(&mut spaces);
+    row
+}
+
+#[cfg(test)]
+mod test {
+    use crate::text;
+
+    use super::*;
+
+    mod split {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let layout = Layout::columns()
+                .child(Constraint::Length(3), text::State::with_text("a"))
+                .child(Constraint::Fill, text::State::with_text("b"))
+                .child(Constraint::Percentage(50), text::State::with_text("c"))
+                .child(Constraint::Fill, text::State::with_text("d"));
+            assert_eq!(vec![3, 2, 5, 1], layout.split(11));
+        }
+
+        #[test]
+        fn test_with_insufficient_space() {
+            let layout = Layout::columns()
+                .child(Constraint::Length(3), text::State::with_text("a"))
+                .child(Constraint::Length(3), text::State::with_text("b"))
+                .child(Constraint::Fill, text::State::with_text("c"));
+            assert_eq!(vec![3, 1, 0], layout.split(4));
+        }
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let pane = Layout::rows()
+                .child(
+                    Constraint::Length(1),
+                    text::State::with_text("title\nignored"),
+                )
+                .child(
+                    Constraint::Fill,
+                    Layout::columns()
+                        .child(Constraint::Length(4), text::State::with_text("a\nb\nc"))
+                        .child(Constraint::Fill, text::State::with_text("left\nright")),
+                )
+                .create_pane(10, 3);
+            assert_eq!(
+                vec!["title", "a   left", "b   right"],
+                pane.extract(3)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_shorter_column() {
+            let pane = Layout::columns()
+                .child(Constraint::Length(2), text::State::with_text("a"))
+                .child(Constraint::Fill, text::State::with_text("b\nc"))
+                .create_pane(4, 3);
+            assert_eq!(
+                vec!["a b", "  c"],
+                pane.extract(3)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
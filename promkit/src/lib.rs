@@ -34,8 +34,16 @@
 //!   - Theme for designing the appearance of the prompt.
 //!     - e.g. cursor, text
 //!   - Validation for user input and error message construction.
-//! - Mouse support (partially)
+//!   - Colored output of other commands, parsed from ANSI escape sequences (`from_ansi`).
+//!   - Opt-in inline markup for styled titles, items and messages (`title_markup`, `from_markup`), e.g. `[bold]Deploy[/] to [fg=red]prod[/]`.
+//!   - Clickable hyperlinks (OSC 8), e.g. `[link=https://example.com]PR #1[/]`.
+//! - Mouse support (partially, `Prompt::mouse_capture`)
 //!   - Allows scrolling through lists with the mouse wheel
+//!   - Allows selecting list items with a click and confirming them with a double click
+//! - Async support (`async` feature)
+//!   - Allows awaiting prompts alongside other futures via `Prompt::run_async`
+//! - Non-interactive fallback
+//!   - Reads answers from stdin when not attached to a terminal, e.g. in CI
 //!
 //! ## Examples/Demos
 //!
@@ -127,8 +135,19 @@ pub use serde_json;
 
 mod core;
 pub use core::*;
+pub mod answers;
+pub mod backend;
+pub mod block;
+mod error;
+pub use error::{Error, Result};
+pub mod fallback;
 pub mod grapheme;
+pub mod headless;
+pub mod help;
+pub mod indicator;
 pub mod jsonz;
+pub mod layout;
+pub mod mouse;
 pub mod pane;
 pub mod preset;
 pub mod style;
@@ -137,16 +156,22 @@ pub mod switch;
 pub mod terminal;
 pub mod validate;
 
-use std::io;
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    crossterm::{
-        cursor,
-        event::{self, Event},
-        execute,
-        terminal::{disable_raw_mode, enable_raw_mode},
-    },
-    pane::Pane,
+    answers::Answers,
+    backend::Backend,
+    crossterm::event::Event,
+    grapheme::StyledGraphemes,
+    pane::{Pane, Region},
     terminal::Terminal,
 };
 
@@ -154,7 +179,7 @@ use crate::{
 ///
 /// This enum is used to indicate whether a prompt should continue running
 /// or quit based on user input or other conditions.
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PromptSignal {
     /// Indicates that the prompt should continue to run and handle further events.
     Continue,
@@ -216,74 +241,582 @@ pub trait Renderer: Finalizer {
     /// that the prompt should continue running, while `PromptSignal::Quit` indicates that
     /// the prompt should terminate its execution.
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal>;
+
+    /// Handles a tick and determines the next action for the prompt.
+    ///
+    /// This method is called periodically at the interval set by
+    /// [`Prompt::tick_interval`], regardless of user input. It allows
+    /// the renderer to animate (e.g. spinners) or refresh its contents.
+    /// The panes are redrawn after each tick.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing a `PromptSignal`, in the same way as `evaluate`.
+    fn on_tick(&mut self) -> anyhow::Result<PromptSignal> {
+        Ok(PromptSignal::Continue)
+    }
+
+    /// Receives where the panes were drawn on screen.
+    ///
+    /// This method is called after every draw with a region for each pane
+    /// returned by `create_panes`, in the same order, so that mouse events
+    /// can be mapped back to the rows of the panes. A region is `None`
+    /// if the pane was not drawn. By default, the regions are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `regions`: Where each pane was drawn, see [`pane::Region`].
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        let _ = regions;
+    }
+
+    /// Provides a single line that replaces the prompt area when the prompt finishes.
+    ///
+    /// This allows "transient" prompts that collapse into a summary
+    /// such as `✔ Pick a branch · main`, instead of leaving their full state
+    /// on screen.
+    ///
+    /// # Parameters
+    ///
+    /// * `ret`: The result produced by `Finalizer::finalize`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the summary line, or `None` (by default) to leave the prompt area as it is.
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        let _ = ret;
+        None
+    }
+
+    /// Produces the result from plain text input without drawing anything.
+    ///
+    /// This method is called instead of the event loop when the prompt
+    /// is not attached to a terminal, e.g. in CI or with piped input,
+    /// so that the same program can be scripted. See [`fallback`]
+    /// for the helpers used by the presets.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The input to read the answer from, i.e. stdin.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the produced result or an error.
+    /// By default, an `Unsupported` I/O error is returned.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let _ = input;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "non-interactive input is not supported by this prompt",
+        )
+        .into())
+    }
+}
+
+/// A trait for renderers that accept messages from outside of the prompt.
+///
+/// This allows background producers (e.g. a thread walking the file system)
+/// to push data into a running prompt through a channel,
+/// see [`Prompt::run_with_receiver`].
+pub trait MessageHandler<M>: Renderer {
+    /// Handles a message and determines the next action for the prompt.
+    ///
+    /// This method is called for each message received while the prompt is running.
+    /// The panes are redrawn after the pending messages are handled.
+    ///
+    /// # Parameters
+    ///
+    /// * `message`: The message that was received.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing a `PromptSignal`, in the same way as `evaluate`.
+    fn on_message(&mut self, message: M) -> anyhow::Result<PromptSignal>;
+}
+
+/// How often the threads reading events and messages check whether the prompt has finished,
+/// i.e. how long finishing a prompt run with a receiver waits for the threads at most.
+const INPUT_THREAD_STOP_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An event or a message for a prompt run with a receiver.
+enum Input<M> {
+    Event(io::Result<Event>),
+    Message(M),
 }
 
 /// Represents a customizable prompt that can handle user input and produce a result.
 ///
 /// This struct encapsulates the rendering logic,
 /// event handling, and result production for a prompt.
+///
+/// It holds the terminal that it draws to, so it is created with [`Prompt::new`]
+/// rather than a struct literal such as `Prompt { renderer }`.
 pub struct Prompt<T: Renderer> {
     pub renderer: T,
+    /// The terminal that the prompt is drawn to.
+    terminal: Terminal,
+    /// The interval at which `Renderer::on_tick` is called, if any.
+    tick_interval: Option<Duration>,
+    /// The id used to look up a pre-seeded answer, if any.
+    id: Option<String>,
+    /// The answers to look up, or `None` to use environment variables.
+    answers: Option<Answers>,
 }
 
 impl<T: Renderer> Drop for Prompt<T> {
     fn drop(&mut self) {
-        execute!(
-            io::stdout(),
-            cursor::Show,
-            event::DisableMouseCapture,
-            cursor::MoveToNextLine(1),
-        )
-        .ok();
-        disable_raw_mode().ok();
+        self.terminal.end_session().ok();
     }
 }
 
 impl<T: Renderer> Prompt<T> {
+    /// Creates a new `Prompt` with the given renderer, drawing to stdout.
+    pub fn new(renderer: T) -> Self {
+        Self {
+            renderer,
+            terminal: Terminal::stdout(),
+            tick_interval: None,
+            id: None,
+            answers: None,
+        }
+    }
+
+    /// Sets the writer that the prompt is drawn to.
+    ///
+    /// This allows drawing the prompt to e.g. stderr or `/dev/tty`,
+    /// so that stdout can be kept clean for piping the result to other commands.
+    /// Raw mode and cursor restoration are applied to the chosen target.
+    ///
+    /// The writer is assumed to be a terminal; use [`Prompt::tty`]
+    /// afterwards for writers such as files and buffers.
+    pub fn writer<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        let backend = Arc::clone(self.terminal.backend());
+        let fullscreen = self.terminal.is_fullscreen();
+        let mouse_capture = self.terminal.is_mouse_capture();
+        self.terminal = Terminal::new(writer);
+        self.terminal.set_backend(backend);
+        self.terminal.set_fullscreen(fullscreen);
+        self.terminal.set_mouse_capture(mouse_capture);
+        self
+    }
+
+    /// Sets the terminal that the size and events are read from.
+    ///
+    /// By default, this is the terminal of the process,
+    /// see [`backend`] for the details.
+    pub fn backend<B: Backend + 'static>(mut self, backend: B) -> Self {
+        self.terminal.set_backend(Arc::new(backend));
+        self
+    }
+
+    /// Sets whether the writer is a terminal, e.g. from `IsTerminal::is_terminal`.
+    ///
+    /// If it is not, the prompt is not drawn and the answer is read from stdin
+    /// through `Renderer::fallback`, as when stdin is not a terminal.
+    pub fn tty(mut self, tty: bool) -> Self {
+        self.terminal.set_tty(tty);
+        self
+    }
+
+    /// Draws the prompt on the alternate screen, using the whole viewport.
+    ///
+    /// This suits pager-like prompts such as large JSON views or long trees.
+    /// The original screen content is restored when the prompt exits,
+    /// including when it is interrupted or fails.
+    pub fn fullscreen(mut self) -> Self {
+        self.terminal.set_fullscreen(true);
+        self
+    }
+
+    /// Captures mouse events, such as clicks and the wheel, while the prompt is running.
+    ///
+    /// The presets move the cursor to the clicked item and confirm it
+    /// on a double click. Note that the terminal cannot select text with the mouse
+    /// while it is captured.
+    pub fn mouse_capture(mut self) -> Self {
+        self.terminal.set_mouse_capture(true);
+        self
+    }
+
+    /// Sets the interval at which `Renderer::on_tick` is called.
+    ///
+    /// By default, no ticks are delivered and the prompt only wakes up on events.
+    pub fn tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = Some(interval);
+        self
+    }
+
+    /// Sets the id used to look up a pre-seeded answer before showing the UI.
+    ///
+    /// Unless other answers are given with [`Prompt::answers`],
+    /// the answer is taken from the `PROMKIT_ANSWER_<ID>` environment variable.
+    /// See [`crate::answers`] for the details.
+    pub fn id<I: Into<String>>(mut self, id: I) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the answers to look up instead of environment variables.
+    pub fn answers(mut self, answers: Answers) -> Self {
+        self.answers = Some(answers);
+        self
+    }
+
     /// Runs the prompt, handling events and producing a result.
     ///
     /// This method initializes the terminal, and enters a loop
     /// to handle events until a quit signal is received.
     /// After exiting the loop, it produces and returns the result.
     ///
+    /// If stdin or the output is not a terminal, the answer is read from stdin
+    /// through `Renderer::fallback` instead.
+    ///
     /// # Returns
     ///
     /// Returns a `Result` containing the produced result or an error.
-    pub fn run(&mut self) -> anyhow::Result<T::Return> {
-        enable_raw_mode()?;
-        execute!(io::stdout(), cursor::Hide)?;
+    pub fn run(&mut self) -> Result<T::Return> {
+        if let Some(ret) = self.preseeded() {
+            return ret;
+        }
+        if !self.terminal.is_interactive() {
+            return self.fallback();
+        }
+        self.prepare()?;
+        let ret = self.read_events();
+        self.finish(ret)
+    }
 
-        let size = crossterm::terminal::size()?;
-        let panes = self.renderer.create_panes(size.0, size.1);
-        let mut terminal = Terminal::start_session(&panes)?;
-        terminal.draw(&panes)?;
+    /// Runs the prompt while receiving messages from the given channel.
+    ///
+    /// This works like [`Prompt::run`], but additionally passes every message
+    /// sent through the channel to `MessageHandler::on_message` and redraws,
+    /// so that data can be streamed into the prompt while it is open.
+    /// The prompt keeps running after all senders are dropped.
+    ///
+    /// Events are read on a separate thread, so that the prompt sleeps
+    /// until either an event or a message arrives.
+    /// The receiver is dropped when the prompt finishes, so that later sends fail
+    /// instead of the messages being lost silently.
+    ///
+    /// If stdin or the output is not a terminal, only the messages that have
+    /// already been sent are handled, so that a producer that never stops
+    /// (e.g. a file watcher) does not keep the prompt waiting,
+    /// and then the answer is read from stdin through `Renderer::fallback`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the produced result or an error.
+    pub fn run_with_receiver<M>(&mut self, receiver: mpsc::Receiver<M>) -> Result<T::Return>
+    where
+        T: MessageHandler<M>,
+        M: Send + 'static,
+    {
+        if let Some(ret) = self.preseeded() {
+            return ret;
+        }
+        if !self.terminal.is_interactive() {
+            for message in receiver.try_iter() {
+                if self.renderer.on_message(message)? == PromptSignal::Quit {
+                    return Ok(self.renderer.finalize()?);
+                }
+            }
+            return self.fallback();
+        }
+        self.prepare()?;
+        let ret = self.read_inputs(receiver);
+        self.finish(ret)
+    }
 
+    /// Runs the prompt asynchronously, handling events and producing a result.
+    ///
+    /// This is the async counterpart of [`Prompt::run`]. Events are read from
+    /// crossterm's `EventStream` instead of blocking on `event::read`,
+    /// so the prompt can be awaited alongside other futures
+    /// (e.g. with `tokio::select!`) and cancelled by dropping the future.
+    /// Note that the terminal is restored when the `Prompt` itself is dropped,
+    /// and that events are read from crossterm even if another backend is set.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the produced result or an error.
+    #[cfg(feature = "async")]
+    pub async fn run_async(&mut self) -> Result<T::Return> {
+        if let Some(ret) = self.preseeded() {
+            return ret;
+        }
+        if !self.terminal.is_interactive() {
+            return self.fallback();
+        }
+        self.prepare()?;
+        let ret = self.read_events_async().await;
+        self.finish(ret)
+    }
+
+    /// Produces the result from the pre-seeded answer for the id, if any.
+    ///
+    /// If the answer does not pass validation, the UI is shown instead
+    /// as long as the prompt is interactive.
+    fn preseeded(&mut self) -> Option<Result<T::Return>> {
+        let id = self.id.as_deref()?;
+        let answer = match &self.answers {
+            Some(answers) => answers.get(id).map(str::to_string),
+            None => Answers::from_env().get(id).map(str::to_string),
+        }?;
+        // Terminate the answer as a line so that an empty answer is not read as EOF.
+        match self
+            .renderer
+            .fallback(&mut format!("{}\n", answer).as_bytes())
+            .map_err(Error::from)
+        {
+            Err(Error::Validation(_)) if self.terminal.is_interactive() => None,
+            ret => Some(ret),
+        }
+    }
+
+    /// Reads the answer from stdin without drawing anything.
+    fn fallback(&mut self) -> Result<T::Return> {
+        Ok(self.renderer.fallback(&mut io::stdin().lock())?)
+    }
+
+    /// Starts a terminal session and draws the initial state of the renderer.
+    fn prepare(&mut self) -> anyhow::Result<()> {
+        self.terminal.start_session()?;
+        self.draw()
+    }
+
+    /// Reads events until the renderer signals to quit.
+    fn read_events(&mut self) -> anyhow::Result<()> {
+        let backend = Arc::clone(self.terminal.backend());
+        let mut last_tick = Instant::now();
         loop {
-            let ev = event::read()?;
-
-            match &ev {
-                Event::Resize(_, _) => {
-                    terminal.position = (0, 0);
-                    crossterm::execute!(
-                        io::stdout(),
-                        crossterm::terminal::Clear(crossterm::terminal::ClearType::Purge),
-                    )?;
+            if let Some(interval) = self.tick_interval {
+                // Tick if no event arrives before the interval elapses.
+                let timeout = interval.saturating_sub(last_tick.elapsed());
+                if timeout.is_zero() || !backend.poll(timeout)? {
+                    last_tick = Instant::now();
+                    if self.handle_tick()? == PromptSignal::Quit {
+                        return Ok(());
+                    }
+                    continue;
                 }
-                _ => {
-                    if self.renderer.evaluate(&ev)? == PromptSignal::Quit {
-                        // Renderer has a possibility to disable the cursor color to indicate termination,
-                        // and so ensure to display the state of Renderer at the end.
-                        terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
-                        break;
+            }
+            if self.handle_event(&backend.read()?)? == PromptSignal::Quit {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reads events and messages until the renderer signals to quit.
+    ///
+    /// Events are read on a separate thread and sent through the same channel
+    /// as the messages, so that waiting for the next input blocks on a single channel.
+    fn read_inputs<M>(&mut self, receiver: mpsc::Receiver<M>) -> anyhow::Result<()>
+    where
+        T: MessageHandler<M>,
+        M: Send + 'static,
+    {
+        let (sender, inputs) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader = thread::spawn({
+            let backend = Arc::clone(self.terminal.backend());
+            let sender = sender.clone();
+            let stop = Arc::clone(&stop);
+            move || forward_events(&*backend, &sender, &stop)
+        });
+        let forwarder = thread::spawn({
+            let stop = Arc::clone(&stop);
+            move || forward_messages(&receiver, &sender, &stop)
+        });
+
+        let ret = self.handle_inputs(&inputs);
+        // Wait for the threads, so that the reader does not take the events of the next prompt
+        // and the receiver is dropped rather than taking messages that are never handled.
+        stop.store(true, Ordering::Relaxed);
+        reader.join().ok();
+        forwarder.join().ok();
+        ret
+    }
+
+    /// Handles events, messages and ticks until the renderer signals to quit.
+    fn handle_inputs<M>(&mut self, inputs: &mpsc::Receiver<Input<M>>) -> anyhow::Result<()>
+    where
+        T: MessageHandler<M>,
+    {
+        let mut last_tick = Instant::now();
+        let mut pending = None;
+        loop {
+            let input = match pending.take() {
+                Some(input) => input,
+                None => match self.tick_interval {
+                    Some(interval) => {
+                        if last_tick.elapsed() >= interval {
+                            last_tick = Instant::now();
+                            if self.handle_tick()? == PromptSignal::Quit {
+                                return Ok(());
+                            }
+                        }
+                        match inputs.recv_timeout(interval.saturating_sub(last_tick.elapsed())) {
+                            Ok(input) => input,
+                            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                            Err(e) => return Err(e.into()),
+                        }
                     }
+                    None => inputs.recv()?,
+                },
+            };
+
+            let signal = match input {
+                Input::Event(ev) => self.handle_event(&ev?)?,
+                Input::Message(message) => {
+                    // Handle the messages that are already pending at once, and draw only once.
+                    let mut signal = self.renderer.on_message(message)?;
+                    while signal == PromptSignal::Continue {
+                        match inputs.try_recv() {
+                            Ok(Input::Message(message)) => {
+                                signal = self.renderer.on_message(message)?
+                            }
+                            Ok(input) => {
+                                pending = Some(input);
+                                break;
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                    self.draw()?;
+                    signal
                 }
+            };
+            if signal == PromptSignal::Quit {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reads events from `EventStream` until the renderer signals to quit.
+    #[cfg(feature = "async")]
+    async fn read_events_async(&mut self) -> anyhow::Result<()> {
+        use futures::{
+            future::{self, Either},
+            StreamExt,
+        };
+
+        let mut stream = crossterm::event::EventStream::new();
+        let mut last_tick = Instant::now();
+        loop {
+            let next = match self.tick_interval {
+                Some(interval) => {
+                    let delay =
+                        futures_timer::Delay::new(interval.saturating_sub(last_tick.elapsed()));
+                    match future::select(stream.next(), delay).await {
+                        Either::Left((ev, _)) => Some(ev),
+                        Either::Right(_) => None,
+                    }
+                }
+                None => Some(stream.next().await),
+            };
+            let signal = match next {
+                Some(Some(ev)) => self.handle_event(&ev?)?,
+                Some(None) => {
+                    return Err(anyhow::anyhow!(io::Error::from(
+                        io::ErrorKind::UnexpectedEof
+                    )))
+                }
+                None => {
+                    last_tick = Instant::now();
+                    self.handle_tick()?
+                }
+            };
+            if signal == PromptSignal::Quit {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Passes a tick to the renderer and redraws the panes.
+    fn handle_tick(&mut self) -> anyhow::Result<PromptSignal> {
+        let signal = self.renderer.on_tick()?;
+        self.draw()?;
+        Ok(signal)
+    }
+
+    /// Passes an event to the renderer and redraws the panes.
+    fn handle_event(&mut self, ev: &Event) -> anyhow::Result<PromptSignal> {
+        let signal = match ev {
+            Event::Resize(_, _) => {
+                self.terminal.reset()?;
+                PromptSignal::Continue
             }
+            _ => self.renderer.evaluate(ev)?,
+        };
+
+        // Renderer has a possibility to disable the cursor color to indicate termination,
+        // and so ensure to display the state of Renderer at the end.
+        self.draw()?;
+        Ok(signal)
+    }
+
+    /// Draws the current state of the renderer.
+    fn draw(&mut self) -> anyhow::Result<()> {
+        let (width, height) = self.terminal.size()?;
+        self.terminal
+            .draw(&self.renderer.create_panes(width, height))?;
+        self.renderer.on_draw(self.terminal.regions());
+        Ok(())
+    }
 
-            let size = crossterm::terminal::size()?;
-            terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+    /// Ends the terminal session and produces the result of the renderer.
+    ///
+    /// If the renderer provides a summary of the result,
+    /// the prompt area is replaced with it.
+    fn finish(&mut self, ret: anyhow::Result<()>) -> Result<T::Return> {
+        if let Err(e) = ret {
+            self.terminal.end_session().ok();
+            return Err(e.into());
+        }
+        let ret = self.renderer.finalize();
+        let summary = ret.as_ref().ok().and_then(|ret| self.renderer.summary(ret));
+        match summary {
+            Some(summary) => self.terminal.end_session_with_summary(summary)?,
+            None => self.terminal.end_session()?,
         }
+        Ok(ret?)
+    }
+}
 
-        disable_raw_mode()?;
-        self.renderer.finalize()
+/// Sends the events read from the backend through the channel
+/// until `stop` is set, reading fails or the channel is closed.
+fn forward_events<M>(backend: &dyn Backend, sender: &mpsc::Sender<Input<M>>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        let ev = match backend.poll(INPUT_THREAD_STOP_INTERVAL) {
+            Ok(false) => continue,
+            // The event is left for the next reader if the prompt has finished meanwhile.
+            Ok(true) if stop.load(Ordering::Relaxed) => return,
+            Ok(true) => backend.read(),
+            Err(e) => Err(e),
+        };
+        let failed = ev.is_err();
+        if sender.send(Input::Event(ev)).is_err() || failed {
+            return;
+        }
+    }
+}
+
+/// Sends the messages received from `receiver` through the channel
+/// until `stop` is set or either of the channels is closed.
+fn forward_messages<M>(
+    receiver: &mpsc::Receiver<M>,
+    sender: &mpsc::Sender<Input<M>>,
+    stop: &AtomicBool,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        match receiver.recv_timeout(INPUT_THREAD_STOP_INTERVAL) {
+            Ok(message) => {
+                if sender.send(Input::Message(message)).is_err() {
+                    return;
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
     }
 }
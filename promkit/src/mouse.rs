@@ -0,0 +1,132 @@
+//! Clicks on panes.
+//!
+//! Terminals only report presses of mouse buttons, so `State` remembers the last
+//! click on a pane to recognize double clicks, and maps the clicked screen row
+//! to a row of the pane through the `Region` where the pane was drawn last.
+//! Mouse events are only delivered with `Prompt::mouse_capture`.
+//!
+//! ```ignore
+//! fn on_draw(&mut self, regions: &[Option<Region>]) {
+//!     self.mouse_state.region = regions.get(1).copied().flatten();
+//! }
+//!
+//! fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+//!     match self.mouse_state.click(event) {
+//!         Some(Click::Single { row, width }) => { /* move to the item at the row */ }
+//!         Some(Click::Double) => return Ok(PromptSignal::Quit),
+//!         None => { /* handle other events */ }
+//!     }
+//!     Ok(PromptSignal::Continue)
+//! }
+//! ```
+use std::time::{Duration, Instant};
+
+use crate::{
+    crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind},
+    pane::Region,
+};
+
+/// The maximum interval between the two clicks of a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A click with the left button on a pane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Click {
+    /// A click on a row of the pane.
+    Single {
+        /// The clicked row of the layout of the pane.
+        row: usize,
+        /// The width of the pane, which the row was laid out for.
+        width: u16,
+    },
+    /// A second click at the same position shortly after the first one.
+    Double,
+}
+
+/// Represents the state of the clicks on a pane.
+#[derive(Clone, Debug, Default)]
+pub struct State {
+    /// Where the pane was drawn last, or `None` if it was not drawn.
+    pub region: Option<Region>,
+    /// The time and position (column and row) of the last click.
+    last: Option<(Instant, u16, u16)>,
+}
+
+impl State {
+    /// Returns the click on the pane that the event represents, if any.
+    ///
+    /// Events other than presses of the left button, and clicks outside
+    /// of the rows of the pane (including its footer), are ignored.
+    pub fn click(&mut self, event: &Event) -> Option<Click> {
+        let Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) = event
+        else {
+            return None;
+        };
+        let region = self.region?;
+        let layout_row = region.row_at(*row)?;
+
+        let now = Instant::now();
+        if let Some((at, last_column, last_row)) = self.last.take() {
+            if (last_column, last_row) == (*column, *row)
+                && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+            {
+                return Some(Click::Double);
+            }
+        }
+        self.last = Some((now, *column, *row));
+        Some(Click::Single {
+            row: layout_row,
+            width: region.width,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod click {
+        use crate::crossterm::event::KeyModifiers;
+
+        use super::super::*;
+
+        fn left(column: u16, row: u16) -> Event {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        }
+
+        #[test]
+        fn test() {
+            let mut state = State {
+                region: Some(Region {
+                    top: 2,
+                    width: 10,
+                    start: 0,
+                    height: 3,
+                }),
+                ..Default::default()
+            };
+            assert_eq!(None, state.click(&left(0, 1)));
+            assert_eq!(
+                Some(Click::Single { row: 1, width: 10 }),
+                state.click(&left(0, 3))
+            );
+            assert_eq!(Some(Click::Double), state.click(&left(0, 3)));
+            assert_eq!(
+                Some(Click::Single { row: 1, width: 10 }),
+                state.click(&left(0, 3))
+            );
+            assert_eq!(
+                Some(Click::Single { row: 2, width: 10 }),
+                state.click(&left(0, 4))
+            );
+        }
+    }
+}
@@ -1,4 +1,65 @@
-use crate::grapheme::StyledGraphemes;
+use crate::{crossterm::cursor::SetCursorStyle, grapheme::StyledGraphemes, indicator::Track};
+
+/// The shape of the terminal cursor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorShape {
+    /// A block covering the whole cell.
+    #[default]
+    Block,
+    /// A vertical bar at the left of the cell.
+    Bar,
+    /// A horizontal line at the bottom of the cell.
+    Underline,
+}
+
+impl From<CursorShape> for SetCursorStyle {
+    fn from(shape: CursorShape) -> Self {
+        match shape {
+            CursorShape::Block => SetCursorStyle::SteadyBlock,
+            CursorShape::Bar => SetCursorStyle::SteadyBar,
+            CursorShape::Underline => SetCursorStyle::SteadyUnderScore,
+        }
+    }
+}
+
+/// The position where the real terminal cursor is placed within a pane.
+///
+/// The cursor is placed there even if it is hidden, so that e.g.
+/// IME composition windows and screen magnifiers follow the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Caret {
+    /// The row within the layout of the pane.
+    pub row: usize,
+    /// The column in cells.
+    pub column: usize,
+    /// The shape of the cursor, or `None` to keep the cursor hidden.
+    pub shape: Option<CursorShape>,
+}
+
+/// Where the rows of a pane were drawn on screen.
+///
+/// This allows renderers to map mouse coordinates back to rows of their panes,
+/// see `Renderer::on_draw`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// The screen row where the first row of the pane was drawn.
+    pub top: u16,
+    /// The width of the pane in cells.
+    pub width: u16,
+    /// The index of the first row of the layout that was drawn.
+    pub start: usize,
+    /// The number of rows of the layout that were drawn, not counting the footer.
+    pub height: usize,
+}
+
+impl Region {
+    /// Returns the row of the layout drawn at the given screen row,
+    /// or `None` if the row is outside of the region or shows the footer.
+    pub fn row_at(&self, row: u16) -> Option<usize> {
+        let row = (row as usize).checked_sub(self.top as usize)?;
+        (row < self.height).then_some(self.start + row)
+    }
+}
 
 #[derive(Clone)]
 pub struct Pane {
@@ -8,6 +69,20 @@ pub struct Pane {
     /// The offset from the top of the pane, used when extracting graphemes to display.
     /// This value determines the starting point for grapheme extraction, allowing for scrolling behavior.
     offset: usize,
+    /// The position of the terminal cursor within the layout, if any.
+    caret: Option<Caret>,
+    /// The number of rows the pane needs to be drawn at all.
+    min_rows: usize,
+    /// The number of rows the pane is given at most, if limited.
+    max_rows: Option<usize>,
+    /// How important the pane is when the viewport is too small.
+    /// Panes with a lower priority are shrunk or dropped first.
+    priority: i32,
+    /// A row always drawn below the extracted rows, if any,
+    /// e.g. the position of the cursor in a list.
+    footer: Option<StyledGraphemes>,
+    /// A scrollbar drawn on the extracted rows, if any.
+    track: Option<Track>,
 }
 
 impl Pane {
@@ -15,7 +90,79 @@ impl Pane {
     /// - `layout`: A vector of `StyledGraphemes` representing the content of the pane.
     /// - `offset`: The initial offset from the top of the pane.
     pub fn new(layout: Vec<StyledGraphemes>, offset: usize) -> Self {
-        Pane { layout, offset }
+        Pane {
+            layout,
+            offset,
+            caret: None,
+            min_rows: 1,
+            max_rows: None,
+            priority: 0,
+            footer: None,
+            track: None,
+        }
+    }
+
+    /// Sets the position where the terminal cursor is placed, or `None` not to place it.
+    pub fn with_caret(mut self, caret: Option<Caret>) -> Self {
+        self.caret = caret;
+        self
+    }
+
+    /// Returns the position where the terminal cursor is placed within the layout, if any.
+    pub fn caret(&self) -> Option<Caret> {
+        self.caret
+    }
+
+    /// Sets the number of rows the pane needs to be drawn at all (1 by default).
+    /// If the viewport cannot give the pane this many rows, the pane is dropped.
+    pub fn with_min_rows(mut self, rows: usize) -> Self {
+        self.min_rows = rows;
+        self
+    }
+
+    /// Sets the number of rows the pane is given at most.
+    pub fn with_max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows);
+        self
+    }
+
+    /// Sets the priority of the pane (0 by default).
+    /// When the viewport is too small, panes with a lower priority are
+    /// shrunk first and dropped first, and panes with the same priority
+    /// are shrunk from the bottom. Of those, the pane that frees just enough rows
+    /// for the others is dropped, or else the bottom-most one.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets a row that is always drawn below the rows extracted from the layout,
+    /// as long as the viewport has room for both.
+    pub fn with_footer(mut self, footer: StyledGraphemes) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// Sets a scrollbar drawn on the rows extracted from the layout.
+    pub(crate) fn with_track(mut self, track: Track) -> Self {
+        self.track = Some(track);
+        self
+    }
+
+    /// Returns the number of rows the pane needs to be drawn at all.
+    pub fn min_rows(&self) -> usize {
+        self.min_rows.min(self.desired_rows())
+    }
+
+    /// Returns the number of rows the pane would take if there were enough space.
+    pub fn desired_rows(&self) -> usize {
+        let rows = self.layout.len() + self.footer.iter().count();
+        self.max_rows.map_or(rows, |max| max.min(rows))
+    }
+
+    /// Returns the priority of the pane.
+    pub fn priority(&self) -> i32 {
+        self.priority
     }
 
     pub fn visible_row_count(&self) -> usize {
@@ -24,38 +171,87 @@ impl Pane {
 
     /// Checks if the pane is empty.
     pub fn is_empty(&self) -> bool {
-        self.layout.is_empty()
+        self.layout.is_empty() && self.footer.is_none()
     }
 
-    pub fn extract(&self, viewport_height: usize) -> Vec<StyledGraphemes> {
-        let lines = self.layout.len().min(viewport_height);
+    /// Returns the number of rows of the layout shown within a viewport
+    /// of the specified height, leaving a row for the footer if possible.
+    fn content_height(&self, viewport_height: usize) -> usize {
+        match self.footer {
+            Some(_) if viewport_height >= 2 || self.layout.is_empty() => {
+                viewport_height.saturating_sub(1)
+            }
+            _ => viewport_height,
+        }
+    }
+
+    /// Returns the range of rows of the layout that are extracted
+    /// within a viewport of the specified height.
+    fn visible_range(&self, viewport_height: usize) -> (usize, usize) {
+        let lines = self.layout.len().min(self.content_height(viewport_height));
         let mut start = self.offset;
         let end = self.offset + lines;
         if end > self.layout.len() {
             start = self.layout.len().saturating_sub(lines);
         }
+        (start, end)
+    }
 
-        self.layout
+    /// Returns the position of the terminal cursor relative to the rows
+    /// extracted by `extract` with the same viewport height,
+    /// or `None` if there is no cursor or it is out of the viewport.
+    pub fn extract_caret(&self, viewport_height: usize) -> Option<Caret> {
+        let (start, end) = self.visible_range(viewport_height);
+        self.caret
+            .filter(|caret| start <= caret.row && caret.row < end)
+            .map(|caret| Caret {
+                row: caret.row - start,
+                ..caret
+            })
+    }
+
+    /// Returns where the rows extracted by `extract` with the same viewport height
+    /// are drawn, given the screen row of the top of the viewport and its width.
+    pub fn extract_region(&self, top: u16, width: u16, viewport_height: usize) -> Region {
+        let (start, end) = self.visible_range(viewport_height);
+        Region {
+            top,
+            width,
+            start,
+            height: end.min(self.layout.len()) - start,
+        }
+    }
+
+    pub fn extract(&self, viewport_height: usize) -> Vec<StyledGraphemes> {
+        let (start, end) = self.visible_range(viewport_height);
+
+        let mut rows = self
+            .layout
             .iter()
             .enumerate()
             .filter(|(i, _)| start <= *i && *i < end)
             .map(|(_, row)| row.clone())
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>();
+        if let Some(track) = &self.track {
+            track.draw(&mut rows);
+        }
+        if let Some(footer) = &self.footer {
+            if rows.len() < viewport_height {
+                rows.push(footer.clone());
+            }
+        }
+        rows
     }
 }
 
 #[cfg(test)]
 mod test {
     mod visible_row_count {
-        use crate::{crossterm::style::ContentStyle, text, PaneFactory};
+        use crate::{text, PaneFactory};
 
         #[test]
         fn test() {
-            let state = text::State {
-                text: Default::default(),
-                style: ContentStyle::default(),
-                lines: None,
-            };
+            let state = text::State::default();
             assert_eq!(0, state.create_pane(10, 10).visible_row_count())
         }
     }
@@ -64,12 +260,19 @@ mod test {
         use super::super::*;
 
         #[test]
+        #[allow(clippy::bool_assert_comparison)]
         fn test() {
             assert_eq!(
                 true,
                 Pane {
                     layout: StyledGraphemes::from("").matrixify(10, 10, 0).0,
                     offset: 0,
+                    caret: None,
+                    min_rows: 1,
+                    max_rows: None,
+                    priority: 0,
+                    footer: None,
+                    track: None,
                 }
                 .is_empty()
             );
@@ -96,6 +299,12 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 0,
+                    caret: None,
+                    min_rows: 1,
+                    max_rows: None,
+                    priority: 0,
+                    footer: None,
+                    track: None,
                 }
                 .extract(3)
             );
@@ -121,6 +330,12 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 0,
+                    caret: None,
+                    min_rows: 1,
+                    max_rows: None,
+                    priority: 0,
+                    footer: None,
+                    track: None,
                 }
                 .extract(10)
             );
@@ -140,6 +355,12 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 2, // indicate `cc`
+                    caret: None,
+                    min_rows: 1,
+                    max_rows: None,
+                    priority: 0,
+                    footer: None,
+                    track: None,
                 }
                 .extract(2)
             );
@@ -163,9 +384,90 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 3, // indicate `dd`
+                    caret: None,
+                    min_rows: 1,
+                    max_rows: None,
+                    priority: 0,
+                    footer: None,
+                    track: None,
                 }
                 .extract(3)
             );
         }
     }
+    mod extract_caret {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let layout = vec![
+                StyledGraphemes::from("aa"),
+                StyledGraphemes::from("bb"),
+                StyledGraphemes::from("cc"),
+                StyledGraphemes::from("dd"),
+            ];
+            let caret = Caret {
+                row: 2,
+                column: 1,
+                shape: Some(CursorShape::Bar),
+            };
+            let pane = Pane::new(layout, 1).with_caret(Some(caret));
+            assert_eq!(Some(Caret { row: 1, ..caret }), pane.extract_caret(2));
+            assert_eq!(None, pane.extract_caret(1));
+        }
+    }
+
+    mod extract_with_footer {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let pane = Pane::new(
+                vec![
+                    StyledGraphemes::from("aa"),
+                    StyledGraphemes::from("bb"),
+                    StyledGraphemes::from("cc"),
+                ],
+                0,
+            )
+            .with_footer(StyledGraphemes::from("1/3"));
+            assert_eq!(
+                vec![StyledGraphemes::from("aa"), StyledGraphemes::from("1/3")],
+                pane.extract(2)
+            );
+            assert_eq!(vec![StyledGraphemes::from("aa")], pane.extract(1));
+            assert_eq!(4, pane.extract(10).len());
+        }
+    }
+
+    mod extract_region {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let pane = Pane::new(
+                vec![
+                    StyledGraphemes::from("aa"),
+                    StyledGraphemes::from("bb"),
+                    StyledGraphemes::from("cc"),
+                ],
+                2,
+            )
+            .with_footer(StyledGraphemes::from("3/3"));
+            let region = pane.extract_region(5, 10, 3);
+            assert_eq!(
+                Region {
+                    top: 5,
+                    width: 10,
+                    start: 1,
+                    height: 2,
+                },
+                region,
+            );
+            assert_eq!(None, region.row_at(4));
+            assert_eq!(Some(1), region.row_at(5));
+            assert_eq!(Some(2), region.row_at(6));
+            assert_eq!(None, region.row_at(7));
+        }
+    }
 }
@@ -1,3 +1,9 @@
+use crate::{
+    crossterm::style::{Attribute, Color},
+    grapheme::StyledGraphemes,
+    style::StyleBuilder,
+};
+
 /// Provides a checkbox interface for multiple options selection.
 pub mod checkbox;
 
@@ -20,3 +26,47 @@ pub mod tree;
 pub mod form;
 
 pub mod text;
+
+/// The priority of title panes, so that titles are shrunk and dropped
+/// before the main widget when the terminal is too small.
+pub(crate) const TITLE_PRIORITY: i32 = -1;
+
+/// Formats the single line that a transient prompt collapses into when it finishes,
+/// from the title of the prompt and the answer.
+///
+/// Instead of leaving its full state on screen, a transient prompt replaces
+/// its area with this line, so that a sequence of prompts leaves a compact log.
+/// The presets are made transient with `transient`, which uses [`default_summary`],
+/// or with `summary`, which takes a custom format. The line is cut to the width
+/// of the terminal.
+pub type Summary = fn(title: &str, answer: &str) -> StyledGraphemes;
+
+/// The default `Summary`, which formats e.g. `✔ Pick a branch · main`.
+pub fn default_summary(title: &str, answer: &str) -> StyledGraphemes {
+    let mut ret = vec![StyledGraphemes::from_str(
+        "✔ ",
+        StyleBuilder::new().fgc(Color::Green).build(),
+    )];
+    if !title.is_empty() {
+        ret.push(StyledGraphemes::from(title).apply_attribute(Attribute::Bold));
+        if !answer.is_empty() {
+            ret.push(StyledGraphemes::from(" · "));
+        }
+    }
+    ret.push(StyledGraphemes::from_str(
+        answer,
+        StyleBuilder::new().fgc(Color::Cyan).build(),
+    ));
+    ret.into_iter().collect()
+}
+
+/// Returns the title of a preset as a single line.
+pub(crate) fn title_text(state: &crate::text::State) -> String {
+    state
+        .text
+        .items()
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -3,6 +3,10 @@ use std::{cell::RefCell, fmt::Display};
 use crate::{
     checkbox,
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
+    indicator::Indicator,
+    preset::{self, Summary},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text::{self, Text},
@@ -16,10 +20,14 @@ pub mod render;
 /// and managing a list of selectable options.
 pub struct Checkbox {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     /// State for the title displayed above the checkbox list.
     title_state: text::State,
     /// State for the checkbox list itself.
     checkbox_state: checkbox::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Checkbox {
@@ -38,6 +46,8 @@ impl Checkbox {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             checkbox_state: checkbox::State {
                 checkbox: checkbox::Checkbox::from_displayable(items),
@@ -47,8 +57,12 @@ impl Checkbox {
                 active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
                 inactive_item_style: StyleBuilder::new().build(),
                 lines: Default::default(),
+                indicator: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
+            summary: None,
         }
     }
 
@@ -60,6 +74,8 @@ impl Checkbox {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             checkbox_state: checkbox::State {
                 checkbox: checkbox::Checkbox::new_with_checked(items),
@@ -69,8 +85,12 @@ impl Checkbox {
                 active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
                 inactive_item_style: StyleBuilder::new().build(),
                 lines: Default::default(),
+                indicator: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
+            summary: None,
         }
     }
 
@@ -80,12 +100,27 @@ impl Checkbox {
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how a title longer than the width is wrapped, e.g. at word boundaries
+    /// with a hanging indent. It is broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self
+    }
+
     /// Sets the cursor symbol used to indicate the current selection.
     pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.checkbox_state.cursor = cursor.as_ref().to_string();
@@ -116,21 +151,50 @@ impl Checkbox {
         self
     }
 
+    /// Sets the scrollbar and position footer shown with the checkbox list.
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.checkbox_state.indicator = indicator;
+        self
+    }
+
     pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>?</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Displays the checkbox prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is a list of selected options.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                checkbox_state: self.checkbox_state,
-            },
-        })
+    ///
+    /// Without a terminal, a line of comma-separated item texts or indices
+    /// is read from stdin instead, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            mouse_state: Default::default(),
+            summary: self.summary,
+            title_state: self.title_state,
+            checkbox_state: self.checkbox_state,
+        }))
     }
 }
@@ -3,6 +3,8 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
+    help::Binding,
+    mouse::Click,
     preset, PromptSignal,
 };
 
@@ -11,15 +13,26 @@ pub type Keymap = fn(
     renderer: &mut preset::checkbox::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "move"),
+    Binding::new("space", "toggle"),
+    Binding::new("enter", "submit"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 /// Default key bindings for the checkbox interface.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
 /// | <kbd>Enter</kbd>       | Exit the interface
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>?</kbd>           | Toggle the help
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
 /// | <kbd>Space</kbd>       | Toggle the checkbox state for the current item
+/// | Click                  | Move the selection to the clicked item
+/// | Double click           | Exit the interface
 pub fn default(
     event: &Event,
     renderer: &mut preset::checkbox::render::Renderer,
@@ -36,7 +49,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('?'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -80,6 +101,17 @@ pub fn default(
             state: KeyEventState::NONE,
         }) => renderer.checkbox_state.checkbox.toggle(),
 
+        // Move the cursor to the clicked row, and confirm with a double click.
+        Event::Mouse(_) => match renderer.mouse_state.click(event) {
+            Some(Click::Single { row, width }) => {
+                if let Some(index) = renderer.checkbox_state.index_at(width, row) {
+                    renderer.checkbox_state.checkbox.move_to(index);
+                }
+            }
+            Some(Click::Double) => return Ok(PromptSignal::Quit),
+            None => (),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
@@ -1,8 +1,15 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
 
 use crate::{
-    checkbox, crossterm::event::Event, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    checkbox,
+    crossterm::event::Event,
+    fallback,
+    grapheme::StyledGraphemes,
+    help, mouse,
+    pane::{Pane, Region},
+    preset::{self, Summary},
+    switch::ActiveKeySwitcher,
+    text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -14,10 +21,16 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key mappings for the renderer.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
+    /// State for the clicks on the list.
+    pub mouse_state: mouse::State,
     /// A title's renderer state.
     pub title_state: text::State,
     /// A checkbox's renderer state.
     pub checkbox_state: checkbox::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -37,8 +50,12 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.checkbox_state.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -46,4 +63,38 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        self.mouse_state.region = regions.get(1).copied().flatten();
+    }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary
+            .map(|summary| summary(&preset::title_text(&self.title_state), &ret.join(", ")))
+    }
+
+    /// Reads a line with comma-separated texts or zero-based indices of items.
+    /// An empty line picks no items.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let items = self
+            .checkbox_state
+            .checkbox
+            .items()
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>();
+        let line = fallback::read_line(input)?;
+        let mut indices = line
+            .split(',')
+            .filter(|answer| !answer.trim().is_empty())
+            .map(|answer| fallback::select(&items, answer))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        // Return the items in the same order as the interactive prompt does.
+        indices.sort_unstable();
+        indices.dedup();
+        Ok(indices
+            .into_iter()
+            .map(|index| items[index].clone())
+            .collect())
+    }
 }
@@ -3,6 +3,8 @@ use std::cell::RefCell;
 use crate::{
     core::Cursor,
     crossterm::style::{Attribute, Attributes},
+    help,
+    preset::{self, Summary},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text_editor, Prompt,
@@ -14,9 +16,13 @@ mod render;
 /// `Form` struct provides functionality for managing multiple text input fields.
 pub struct Form {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     text_editor_states: Vec<text_editor::State>,
     /// Overwrite the default styles of text editor states when unselected.
     overwrite_styles: Vec<render::Style>,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Form {
@@ -39,13 +45,39 @@ impl Form {
             })
             .unzip();
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("F1"),
             text_editor_states,
             overwrite_styles,
+            summary: None,
         }
     }
 
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>F1</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
+    /// Creates a prompt that edits the fields and returns their contents.
+    ///
+    /// Without a terminal, one line per field is read from stdin instead,
+    /// see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
         let default_styles = self
             .text_editor_states
             .iter()
@@ -57,11 +89,13 @@ impl Form {
             .collect();
         let mut renderer = render::Renderer {
             keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            summary: self.summary,
             text_editor_states: Cursor::new(self.text_editor_states, 0, false),
             default_styles,
             overwrite_styles: self.overwrite_styles,
         };
         renderer.overwrite_styles();
-        Ok(Prompt { renderer })
+        Ok(Prompt::new(renderer))
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    help::Binding,
     preset, text_editor, PromptSignal,
 };
 
@@ -8,6 +9,19 @@ pub type Keymap = fn(
     renderer: &mut preset::form::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "switch field"),
+    Binding::new("enter", "submit"),
+    Binding::new("←→", "move cursor"),
+    Binding::new("ctrl+a/e", "start/end"),
+    Binding::new("alt+b/f", "previous/next word"),
+    Binding::new("backspace", "delete"),
+    Binding::new("ctrl+w/alt+d", "delete word"),
+    Binding::new("ctrl+u", "clear"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 pub fn default(
     event: &Event,
     renderer: &mut preset::form::render::Renderer,
@@ -26,7 +40,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::F(1),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -160,6 +182,9 @@ pub fn default(
         }
 
         // Input char.
+        Event::Paste(text) => renderer.text_editor_states.contents_mut()[current_position]
+            .texteditor
+            .paste(text),
         Event::Key(KeyEvent {
             code: KeyCode::Char(ch),
             modifiers: KeyModifiers::NONE,
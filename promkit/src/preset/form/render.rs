@@ -1,9 +1,13 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
 
 use crate::{
     core::Cursor,
     crossterm::{event::Event, style::ContentStyle},
+    fallback,
+    grapheme::StyledGraphemes,
+    help,
     pane::Pane,
+    preset::Summary,
     switch::ActiveKeySwitcher,
     text_editor, PaneFactory, PromptSignal,
 };
@@ -24,12 +28,16 @@ pub struct Style {
 pub struct Renderer {
     /// A mutable reference to a key switcher that manages active key mappings.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
     /// Cursor managing the state of multiple text editors.
     pub text_editor_states: Cursor<Vec<text_editor::State>>,
     /// Default styles applied to text editors.
     pub default_styles: Vec<Style>,
     /// Styles applied to text editors when they are unselected.
     pub overwrite_styles: Vec<Style>,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -69,10 +77,23 @@ impl Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        let current_position = self.text_editor_states.position();
         self.text_editor_states
             .contents()
             .iter()
-            .map(|state| state.create_pane(width, height))
+            .enumerate()
+            .map(|(i, state)| {
+                let pane = state.create_pane(width, height);
+                // Only the selected editor places the terminal cursor.
+                if i == current_position {
+                    pane
+                } else {
+                    pane.with_caret(None)
+                }
+            })
+            .chain([self
+                .help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height)])
             .collect()
     }
 
@@ -82,4 +103,15 @@ impl crate::Renderer for Renderer {
         self.overwrite_styles();
         signal
     }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary.map(|summary| summary("", &ret.join(", ")))
+    }
+
+    /// Reads a line for each text editor in order.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        (0..self.text_editor_states.contents().len())
+            .map(|_| fallback::read_line(input))
+            .collect()
+    }
 }
@@ -2,8 +2,12 @@ use std::cell::RefCell;
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
+    indicator::Indicator,
     jsonstream::{self, JsonStream},
     jsonz::format::RowFormatter,
+    preset::{self, Summary},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text::{self, Text},
@@ -16,8 +20,12 @@ pub mod render;
 /// Represents a JSON preset for rendering JSON data and titles with customizable styles.
 pub struct Json {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     title_state: text::State,
     json_state: jsonstream::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Json {
@@ -29,6 +37,8 @@ impl Json {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             json_state: jsonstream::State {
                 stream,
@@ -49,8 +59,12 @@ impl Json {
                     indent: 2,
                 },
                 lines: Default::default(),
+                indicator: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
+            summary: None,
         }
     }
 
@@ -60,18 +74,39 @@ impl Json {
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how a title longer than the width is wrapped, e.g. at word boundaries
+    /// with a hanging indent. It is broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self
+    }
+
     /// Sets the number of lines to be used for rendering the JSON data.
     pub fn json_lines(mut self, lines: usize) -> Self {
         self.json_state.lines = Some(lines);
         self
     }
 
+    /// Sets the scrollbar and position footer shown with the JSON data.
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.json_state.indicator = indicator;
+        self
+    }
+
     /// Sets the indentation level for rendering the JSON data.
     pub fn indent(mut self, indent: usize) -> Self {
         self.json_state.formatter.indent = indent;
@@ -95,14 +130,36 @@ impl Json {
         self
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>?</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Creates a prompt based on the current configuration of the `Json` instance.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                json_state: self.json_state,
-            },
-        })
+    ///
+    /// Without a terminal, the prompt returns immediately without reading stdin.
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            mouse_state: Default::default(),
+            summary: self.summary,
+            title_state: self.title_state,
+            json_state: self.json_state,
+        }))
     }
 }
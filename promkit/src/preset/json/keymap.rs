@@ -3,6 +3,8 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
+    help::Binding,
+    mouse::Click,
     preset, PromptSignal,
 };
 
@@ -11,15 +13,26 @@ pub type Keymap = fn(
     renderer: &mut preset::json::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "move"),
+    Binding::new("space", "fold/unfold"),
+    Binding::new("enter", "quit"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 /// Default key bindings for JSON navigation and manipulation.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
 /// | <kbd>Enter</kbd>       | Exit the JSON viewer
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>?</kbd>           | Toggle the help
 /// | <kbd>↑</kbd>           | Move the cursor up to the previous node
 /// | <kbd>↓</kbd>           | Move the cursor down to the next node
 /// | <kbd>Space</kbd>       | Toggle fold/unfold on the current node
+/// | Click                  | Move the cursor to the clicked node
+/// | Double click           | Exit the JSON viewer
 pub fn default(
     event: &Event,
     renderer: &mut preset::json::render::Renderer,
@@ -36,7 +49,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('?'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -79,6 +100,18 @@ pub fn default(
             renderer.json_state.stream.toggle();
         }
 
+        // Move the cursor to the clicked row, and confirm with a double click.
+        Event::Mouse(_) => match renderer.mouse_state.click(event) {
+            // Each node is drawn in a single row, starting at the cursor.
+            Some(Click::Single { row, .. }) => {
+                for _ in 0..row {
+                    renderer.json_state.stream.down();
+                }
+            }
+            Some(Click::Double) => return Ok(PromptSignal::Quit),
+            None => (),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
@@ -1,8 +1,13 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
 
 use crate::{
-    crossterm::event::Event, jsonstream, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event,
+    grapheme::StyledGraphemes,
+    help, jsonstream, mouse,
+    pane::{Pane, Region},
+    preset::{self, Summary},
+    switch::ActiveKeySwitcher,
+    text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -12,10 +17,16 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key mappings specific to this renderer.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
+    /// State for the clicks on the JSON.
+    pub mouse_state: mouse::State,
     /// A renderer used for the title.
     pub title_state: text::State,
     /// A renderer used for JSON content.
     pub json_state: jsonstream::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -29,8 +40,12 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.json_state.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -38,4 +53,18 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        self.mouse_state.region = regions.get(1).copied().flatten();
+    }
+
+    fn summary(&self, _: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary
+            .map(|summary| summary(&preset::title_text(&self.title_state), ""))
+    }
+
+    /// Does nothing, since there is no answer to read.
+    fn fallback(&mut self, _: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        Ok(())
+    }
 }
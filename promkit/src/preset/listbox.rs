@@ -2,7 +2,11 @@ use std::{cell::RefCell, fmt::Display};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
+    indicator::Indicator,
     listbox,
+    preset::{self, Summary},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text::{self, Text},
@@ -15,10 +19,17 @@ pub mod render;
 /// A component for creating and managing a selectable list of options.
 pub struct Listbox {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     /// State for the title displayed above the selectable list.
     title_state: text::State,
     /// State for the selectable list itself.
     listbox_state: listbox::State,
+    /// Whether the items, including the streamed ones,
+    /// are strings with ANSI escape sequences.
+    ansi: bool,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Listbox {
@@ -37,30 +48,74 @@ impl Listbox {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             listbox_state: listbox::State {
                 listbox: listbox::Listbox::from_displayable(items),
                 cursor: String::from("❯ "),
                 active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
                 inactive_item_style: Some(StyleBuilder::new().build()),
+                keep_item_styles: false,
                 lines: Default::default(),
+                indicator: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
+            ansi: false,
+            summary: None,
         }
     }
 
+    /// Constructs a new `Listbox` instance from strings with ANSI escape sequences,
+    /// which are displayed with their colors. The selected option is returned without them.
+    ///
+    /// Items streamed to the prompt as messages are parsed in the same way.
+    pub fn from_ansi<T: AsRef<str>, I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut ret = Self::new(Vec::<String>::new());
+        ret.listbox_state.listbox = listbox::Listbox::from_ansi(items);
+        ret.listbox_state.keep_item_styles = true;
+        ret.ansi = true;
+        ret
+    }
+
+    /// Constructs a new `Listbox` instance from strings with markup tags
+    /// (see `StyledGraphemes::from_markup`). The selected option is returned without them.
+    pub fn from_markup<T: AsRef<str>, I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut ret = Self::new(Vec::<String>::new());
+        ret.listbox_state.listbox = listbox::Listbox::from_markup(items);
+        ret.listbox_state.keep_item_styles = true;
+        ret
+    }
+
     /// Sets the title text displayed above the selectable list.
     pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
         self.title_state.text = Text::from(text);
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how a title longer than the width is wrapped, e.g. at word boundaries
+    /// with a hanging indent. It is broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self
+    }
+
     /// Sets the cursor symbol used to indicate the current selection.
     pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.listbox_state.cursor = cursor.as_ref().to_string();
@@ -85,21 +140,51 @@ impl Listbox {
         self
     }
 
+    /// Sets the scrollbar and position footer shown with the selectable list.
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.listbox_state.indicator = indicator;
+        self
+    }
+
     pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>?</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Displays the select prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                listbox_state: self.listbox_state,
-            },
-        })
+    ///
+    /// Without a terminal, the text or index of an item
+    /// is read from stdin instead, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            mouse_state: Default::default(),
+            summary: self.summary,
+            title_state: self.title_state,
+            listbox_state: self.listbox_state,
+            ansi: self.ansi,
+        }))
     }
 }
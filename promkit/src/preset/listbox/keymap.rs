@@ -3,6 +3,8 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
+    help::Binding,
+    mouse::Click,
     preset, PromptSignal,
 };
 
@@ -11,14 +13,24 @@ pub type Keymap = fn(
     renderer: &mut preset::listbox::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "move"),
+    Binding::new("enter", "select"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 /// Default key bindings for the listbox.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
 /// | <kbd>Enter</kbd>       | Exit the listbox
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>?</kbd>           | Toggle the help
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
+/// | Click                  | Move the selection to the clicked item
+/// | Double click           | Exit the listbox
 pub fn default(
     event: &Event,
     renderer: &mut preset::listbox::render::Renderer,
@@ -35,7 +47,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('?'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -68,6 +88,17 @@ pub fn default(
             renderer.listbox_state.listbox.forward();
         }
 
+        // Move the cursor to the clicked row, and confirm with a double click.
+        Event::Mouse(_) => match renderer.mouse_state.click(event) {
+            Some(Click::Single { row, width }) => {
+                if let Some(index) = renderer.listbox_state.index_at(width, row) {
+                    renderer.listbox_state.listbox.move_to(index);
+                }
+            }
+            Some(Click::Double) => return Ok(PromptSignal::Quit),
+            None => (),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
@@ -1,16 +1,30 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, fmt::Display, io::BufRead};
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event,
+    fallback,
+    grapheme::StyledGraphemes,
+    help, listbox, mouse,
+    pane::{Pane, Region},
+    preset::{self, Summary},
+    switch::ActiveKeySwitcher,
+    text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
 
 pub struct Renderer {
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
+    /// State for the clicks on the list.
+    pub mouse_state: mouse::State,
     pub title_state: text::State,
     pub listbox_state: listbox::State,
+    /// Whether the streamed items are strings with ANSI escape sequences.
+    pub ansi: bool,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -24,8 +38,12 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.listbox_state.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -33,4 +51,39 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        self.mouse_state.region = regions.get(1).copied().flatten();
+    }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary
+            .map(|summary| summary(&preset::title_text(&self.title_state), ret))
+    }
+
+    /// Reads a line with the text or the zero-based index of an item.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let items = self
+            .listbox_state
+            .listbox
+            .items()
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>();
+        let index = fallback::select(&items, &fallback::read_line(input)?)?;
+        Ok(items[index].clone())
+    }
+}
+
+impl<M: Display> crate::MessageHandler<M> for Renderer {
+    fn on_message(&mut self, message: M) -> anyhow::Result<PromptSignal> {
+        if self.ansi {
+            self.listbox_state
+                .listbox
+                .push(StyledGraphemes::from_ansi(message.to_string()));
+        } else {
+            self.listbox_state.listbox.push_string(message.to_string());
+        }
+        Ok(PromptSignal::Continue)
+    }
 }
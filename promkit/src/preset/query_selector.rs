@@ -2,7 +2,12 @@ use std::{cell::RefCell, fmt::Display};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
+    indicator::Indicator,
     listbox::{self, Listbox},
+    pane::CursorShape,
+    preset::{self, Summary},
     snapshot::Snapshot,
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
@@ -19,6 +24,8 @@ pub mod render;
 /// for displaying filtered options based on the input.
 pub struct QuerySelector {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     /// State for the title displayed above the query selection.
     title_state: text::State,
     /// State for the text editor component.
@@ -28,6 +35,11 @@ pub struct QuerySelector {
     /// A filter function to apply to the list box items
     /// based on the text editor input.
     filter: render::Filter,
+    /// Whether the items, including the streamed ones,
+    /// are strings with ANSI escape sequences.
+    ansi: bool,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl QuerySelector {
@@ -53,6 +65,8 @@ impl QuerySelector {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             text_editor_state: text_editor::State {
                 texteditor: Default::default(),
@@ -65,31 +79,70 @@ impl QuerySelector {
                 edit_mode: Default::default(),
                 word_break_chars: Default::default(),
                 lines: Default::default(),
+                cursor_shape: None,
             },
             listbox_state: listbox::State {
                 listbox: Listbox::from_displayable(items),
                 cursor: String::from("❯ "),
                 active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
                 inactive_item_style: Some(StyleBuilder::new().build()),
+                keep_item_styles: false,
                 lines: Default::default(),
+                indicator: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("F1"),
             filter,
+            ansi: false,
+            summary: None,
         }
     }
 
+    /// Constructs a new `QuerySelector` instance from strings with ANSI escape sequences,
+    /// which are displayed with their colors. The filter function is given the items
+    /// without the sequences, and so is the selected option returned.
+    ///
+    /// Items streamed to the prompt as messages are parsed in the same way.
+    pub fn from_ansi<T, I>(items: I, filter: render::Filter) -> Self
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut ret = Self::new(Vec::<String>::new(), filter);
+        ret.listbox_state.listbox = Listbox::from_ansi(items);
+        ret.listbox_state.keep_item_styles = true;
+        ret.ansi = true;
+        ret
+    }
+
     /// Sets the title text displayed above the query selection.
     pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
         self.title_state.text = Text::from(text);
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how a title longer than the width is wrapped, e.g. at word boundaries
+    /// with a hanging indent. It is broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self
+    }
+
     /// Sets the prefix string displayed before the input text in the text editor component.
     pub fn prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
         self.text_editor_state.prefix = prefix.as_ref().to_string();
@@ -114,6 +167,13 @@ impl QuerySelector {
         self
     }
 
+    /// Sets the shape of the terminal cursor shown in the text editor component,
+    /// or `None` to hide it and highlight the active character instead.
+    pub fn cursor_shape(mut self, shape: Option<CursorShape>) -> Self {
+        self.text_editor_state.cursor_shape = shape;
+        self
+    }
+
     /// Sets the editing mode for the text editor component.
     pub fn edit_mode(mut self, mode: Mode) -> Self {
         self.text_editor_state.edit_mode = mode;
@@ -150,23 +210,53 @@ impl QuerySelector {
         self
     }
 
+    /// Sets the scrollbar and position footer shown with the list box component.
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.listbox_state.indicator = indicator;
+        self
+    }
+
     pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>F1</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Displays the query select prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
-                listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
-                filter: self.filter,
-            },
-        })
+    ///
+    /// Without a terminal, the text or index of an item
+    /// is read from stdin instead, regardless of the query, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            mouse_state: Default::default(),
+            summary: self.summary,
+            title_state: self.title_state,
+            text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+            listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
+            filter: self.filter,
+            ansi: self.ansi,
+        }))
     }
 }
@@ -1,5 +1,7 @@
 use crate::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    help::Binding,
+    mouse::Click,
     preset, text_editor, PromptSignal,
 };
 
@@ -8,6 +10,17 @@ pub type Keymap = fn(
     renderer: &mut preset::query_selector::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "move"),
+    Binding::new("enter", "select"),
+    Binding::new("←→", "move cursor"),
+    Binding::new("ctrl+a/e", "start/end"),
+    Binding::new("backspace", "delete"),
+    Binding::new("ctrl+u", "clear"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 pub fn default(
     event: &Event,
     renderer: &mut preset::query_selector::render::Renderer,
@@ -27,7 +40,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::F(1),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -93,6 +114,7 @@ pub fn default(
         }
 
         // Input char.
+        Event::Paste(text) => text_editor_after_mut.texteditor.paste(text),
         Event::Key(KeyEvent {
             code: KeyCode::Char(ch),
             modifiers: KeyModifiers::NONE,
@@ -109,6 +131,17 @@ pub fn default(
             text_editor::Mode::Overwrite => text_editor_after_mut.texteditor.overwrite(*ch),
         },
 
+        // Move the cursor to the clicked row, and confirm with a double click.
+        Event::Mouse(_) => match renderer.mouse_state.click(event) {
+            Some(Click::Single { row, width }) => {
+                if let Some(index) = listbox_after_mut.index_at(width, row) {
+                    listbox_after_mut.listbox.move_to(index);
+                }
+            }
+            Some(Click::Double) => return Ok(PromptSignal::Quit),
+            None => (),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
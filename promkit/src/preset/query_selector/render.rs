@@ -1,9 +1,14 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, fmt::Display, io::BufRead};
 
 use crate::{
     crossterm::event::Event,
+    fallback,
+    grapheme::StyledGraphemes,
+    help,
     listbox::{self, Listbox},
-    pane::Pane,
+    mouse,
+    pane::{Pane, Region},
+    preset::{self, Summary},
     snapshot::Snapshot,
     switch::ActiveKeySwitcher,
     text, text_editor, PaneFactory, PromptSignal,
@@ -21,6 +26,10 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key mappings specific to this renderer.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
+    /// State for the clicks on the list.
+    pub mouse_state: mouse::State,
     /// A title renderer.
     pub title_state: text::State,
     /// Snapshot of the text editor renderer.
@@ -28,6 +37,10 @@ pub struct Renderer {
     /// Snapshot of the listbox renderer.
     pub listbox_snapshot: Snapshot<listbox::State>,
     pub filter: Filter,
+    /// Whether the streamed items are strings with ANSI escape sequences.
+    pub ansi: bool,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -41,9 +54,13 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.text_editor_snapshot.create_pane(width, height),
             self.listbox_snapshot.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -60,19 +77,90 @@ impl crate::Renderer for Renderer {
                 .text_without_cursor()
                 .to_string();
 
-            let list = (self.filter)(
-                &query,
-                &self
-                    .listbox_snapshot
-                    .init()
-                    .listbox
-                    .items()
-                    .iter()
-                    .map(|e| e.to_string())
+            let items = self.listbox_snapshot.init().listbox.items();
+            let texts = items.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            let list = (self.filter)(&query, &texts);
+
+            // The filter only sees the text, so each result is mapped back to
+            // the first item with the same text that is not taken yet, which keeps
+            // the styles of items that share their text apart.
+            let mut taken = vec![false; items.len()];
+            let listbox = Listbox::from_styled_graphemes(
+                list.into_iter()
+                    .map(
+                        |item| match (0..texts.len()).find(|&i| !taken[i] && texts[i] == item) {
+                            Some(i) => {
+                                taken[i] = true;
+                                items[i].clone()
+                            }
+                            None => StyledGraphemes::from(item),
+                        },
+                    )
                     .collect(),
             );
-            self.listbox_snapshot.after_mut().listbox = Listbox::from_displayable(list);
+            self.listbox_snapshot.after_mut().listbox = listbox;
         }
         signal
     }
+
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        self.mouse_state.region = regions.get(2).copied().flatten();
+    }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary
+            .map(|summary| summary(&preset::title_text(&self.title_state), ret))
+    }
+
+    /// Reads a line with the text or the zero-based index of an item,
+    /// chosen from all items regardless of the query.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let items = self
+            .listbox_snapshot
+            .init()
+            .listbox
+            .items()
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>();
+        let index = fallback::select(&items, &fallback::read_line(input)?)?;
+        Ok(items[index].clone())
+    }
+}
+
+impl<M: Display> crate::MessageHandler<M> for Renderer {
+    fn on_message(&mut self, message: M) -> anyhow::Result<PromptSignal> {
+        let styled = if self.ansi {
+            StyledGraphemes::from_ansi(message.to_string())
+        } else {
+            StyledGraphemes::from(message.to_string())
+        };
+        let item = styled.to_string();
+        self.listbox_snapshot
+            .init_mut()
+            .listbox
+            .push(styled.clone());
+
+        // Only the new item is filtered, so that the cursor position is kept.
+        let query = self
+            .text_editor_snapshot
+            .after()
+            .texteditor
+            .text_without_cursor()
+            .to_string();
+        for filtered in (self.filter)(&query, &vec![item.clone()]) {
+            if filtered == item {
+                self.listbox_snapshot
+                    .after_mut()
+                    .listbox
+                    .push(styled.clone());
+            } else {
+                self.listbox_snapshot
+                    .after_mut()
+                    .listbox
+                    .push_string(filtered);
+            }
+        }
+        Ok(PromptSignal::Continue)
+    }
 }
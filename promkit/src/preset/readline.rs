@@ -2,7 +2,11 @@ use std::{cell::RefCell, collections::HashSet};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
     listbox::{self, Listbox},
+    pane::CursorShape,
+    preset::{self, Summary},
     snapshot::Snapshot,
     style::StyleBuilder,
     suggest::Suggest,
@@ -24,6 +28,8 @@ pub mod render;
 /// such as input masking, history, suggestions, and custom styles.
 pub struct Readline {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     /// State for the title displayed above the input field.
     title_state: text::State,
     /// State for the text editor where user input is entered.
@@ -32,21 +38,30 @@ pub struct Readline {
     suggest_state: listbox::State,
     /// Optional validator for input validation with custom error messages.
     validator: Option<ValidatorManager<str>>,
+    /// Whether the error messages contain markup tags.
+    error_message_markup: bool,
     /// State for displaying error messages based on input validation.
     error_message_state: text::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Default for Readline {
     fn default() -> Self {
         Self {
             keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
-                .register("on_suggest", self::keymap::on_suggest),
+                .register("on_suggest", self::keymap::on_suggest)
+                .describe("default", self::keymap::DEFAULT_BINDINGS)
+                .describe("on_suggest", self::keymap::ON_SUGGEST_BINDINGS),
+            help_state: help::State::new("F1"),
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             text_editor_state: text_editor::State {
                 texteditor: Default::default(),
@@ -59,6 +74,7 @@ impl Default for Readline {
                 edit_mode: Default::default(),
                 word_break_chars: HashSet::from([' ']),
                 lines: Default::default(),
+                cursor_shape: None,
             },
             suggest: Default::default(),
             suggest_state: listbox::State {
@@ -71,9 +87,12 @@ impl Default for Readline {
                         .build(),
                 ),
                 inactive_item_style: Some(StyleBuilder::new().fgc(Color::DarkGrey).build()),
+                keep_item_styles: false,
                 lines: Some(3),
+                indicator: Default::default(),
             },
             validator: Default::default(),
+            error_message_markup: false,
             error_message_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
@@ -81,7 +100,10 @@ impl Default for Readline {
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
+            summary: None,
         }
     }
 }
@@ -93,12 +115,28 @@ impl Readline {
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how the title and the error message are wrapped when longer than the width,
+    /// e.g. at word boundaries with a hanging indent. They are broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self.error_message_state.wrap = wrap;
+        self
+    }
+
     /// Enables suggestion functionality with the provided `Suggest` instance.
     pub fn enable_suggest(mut self, suggest: Suggest) -> Self {
         self.suggest = Some(suggest);
@@ -135,6 +173,13 @@ impl Readline {
         self
     }
 
+    /// Sets the shape of the terminal cursor shown in the input field,
+    /// or `None` to hide it and highlight the current character instead.
+    pub fn cursor_shape(mut self, shape: Option<CursorShape>) -> Self {
+        self.text_editor_state.cursor_shape = shape;
+        self
+    }
+
     /// Sets the style for characters that are not currently active in the input field.
     pub fn inactive_char_style(mut self, style: ContentStyle) -> Self {
         self.text_editor_state.inactive_char_style = style;
@@ -174,19 +219,50 @@ impl Readline {
         self
     }
 
+    /// Parses markup tags (see `StyledGraphemes::from_markup`) in the error messages
+    /// of the validator. Without a terminal, the messages are returned without the tags.
+    pub fn error_message_markup(mut self) -> Self {
+        self.error_message_markup = true;
+        self.error_message_state.keep_styles = true;
+        self
+    }
+
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>F1</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Initiates the prompt process,
     /// displaying the configured UI elements and handling user input.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
-                suggest: self.suggest,
-                suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
-                validator: self.validator,
-                error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
-            },
-        })
+    ///
+    /// Without a terminal, a line is read from stdin instead
+    /// and checked with the validator, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            summary: self.summary,
+            title_state: self.title_state,
+            text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+            suggest: self.suggest,
+            suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
+            validator: self.validator,
+            error_message_markup: self.error_message_markup,
+            error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
+        }))
     }
 }
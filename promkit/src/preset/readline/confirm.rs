@@ -1,4 +1,4 @@
-use crate::Prompt;
+use crate::{preset::Summary, Prompt};
 
 use super::{render, Readline};
 
@@ -18,20 +18,32 @@ impl Confirm {
             Readline::default()
                 .prefix(format!("{} (y/n) ", text.as_ref()))
                 .validator(
-                    |text| -> bool {
-                        ["yes", "no", "y", "n", "Y", "N"]
-                            .iter()
-                            .any(|yn| *yn == text)
-                    },
+                    |text| -> bool { ["yes", "no", "y", "n", "Y", "N"].contains(&text) },
                     |_| String::from("Please type 'y' or 'n' as an answer"),
                 ),
         )
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(self) -> Self {
+        Self(self.0.transient())
+    }
+
+    /// Makes the prompt transient with the given format, which is given the question as the title.
+    pub fn summary(self, summary: Summary) -> Self {
+        Self(self.0.summary(summary))
+    }
+
     /// Displays the confirmation prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the user's input.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    ///
+    /// The help of the key bindings is toggled with <kbd>F1</kbd>,
+    /// since `?` is typed into the answer.
+    ///
+    /// Without a terminal, a line is read from stdin instead
+    /// and must be one of `y`, `yes`, `n`, `no`, `Y` or `N`, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
         self.0.prompt()
     }
 }
@@ -2,6 +2,7 @@ use crossterm::style::ContentStyle;
 
 use crate::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    help::Binding,
     listbox::Listbox,
     preset,
     text::Text,
@@ -13,12 +14,37 @@ pub type Keymap = fn(
     renderer: &mut preset::readline::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("enter", "submit"),
+    Binding::new("tab", "complete"),
+    Binding::new("↑↓", "history"),
+    Binding::new("←→", "move"),
+    Binding::new("ctrl+a/e", "start/end"),
+    Binding::new("alt+b/f", "previous/next word"),
+    Binding::new("backspace", "delete"),
+    Binding::new("ctrl+w/alt+d", "delete word"),
+    Binding::new("ctrl+u", "clear"),
+    Binding::new("ctrl+d", "end of input"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
+/// Descriptions of the bindings of `on_suggest`, shown in the help.
+pub const ON_SUGGEST_BINDINGS: &[Binding] = &[
+    Binding::new("tab/↓", "next suggestion"),
+    Binding::new("↑", "previous suggestion"),
+    Binding::new("other keys", "back to editing"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 /// Default key bindings for the text editor.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
 /// | <kbd>Enter</kbd>       | Exit the editor if input is valid, otherwise show error message
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>F1</kbd>          | Toggle the help
+/// | <kbd>Ctrl + D</kbd>    | Signal the end of input if the line is empty
 /// | <kbd>←</kbd>           | Move the cursor one character to the left
 /// | <kbd>→</kbd>           | Move the cursor one character to the right
 /// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the line
@@ -57,8 +83,12 @@ pub fn default(
                 .map(|validator| {
                     let valid = validator.validate(&text);
                     if !valid {
-                        error_message_after_mut.text =
-                            Text::from(validator.generate_error_message(&text));
+                        let message = validator.generate_error_message(&text);
+                        error_message_after_mut.text = if renderer.error_message_markup {
+                            Text::from_markup(message)
+                        } else {
+                            Text::from(message)
+                        };
                     }
                     valid
                 })
@@ -82,7 +112,27 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::F(1),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('d'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) if text_editor_after_mut
+            .texteditor
+            .text_without_cursor()
+            .is_empty() =>
+        {
+            return Err(crate::Error::Eof.into())
+        }
 
         Event::Key(KeyEvent {
             code: KeyCode::Tab,
@@ -214,6 +264,9 @@ pub fn default(
             }
         }
 
+        // Paste text.
+        Event::Paste(text) => text_editor_after_mut.texteditor.paste(text),
+
         // Input char.
         Event::Key(KeyEvent {
             code: KeyCode::Char(ch),
@@ -249,7 +302,27 @@ pub fn on_suggest(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::F(1),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('d'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) if text_editor_after_mut
+            .texteditor
+            .text_without_cursor()
+            .is_empty() =>
+        {
+            return Err(crate::Error::Eof.into())
+        }
 
         Event::Key(KeyEvent {
             code: KeyCode::Tab,
@@ -281,6 +354,14 @@ pub fn on_suggest(
                 .replace(&suggest_after_mut.listbox.get().to_string());
         }
 
+        // Paste text, going back to editing.
+        Event::Paste(text) => {
+            text_editor_after_mut.texteditor.paste(text);
+            suggest_after_mut.listbox = Listbox::from_displayable(Vec::<String>::new());
+
+            renderer.keymap.borrow_mut().switch("default");
+        }
+
         _ => {
             suggest_after_mut.listbox = Listbox::from_displayable(Vec::<String>::new());
 
@@ -1,5 +1,7 @@
 use crate::{
     crossterm::style::ContentStyle,
+    grapheme::Wrap,
+    preset::Summary,
     validate::{ErrorMessageGenerator, Validator},
     Prompt,
 };
@@ -24,12 +26,24 @@ impl Password {
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`).
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self = Password(self.0.title_markup(text));
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self = Password(self.0.title_style(style));
         self
     }
 
+    /// Sets how the title and the error message are wrapped, see `Readline::wrap`.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self = Password(self.0.wrap(wrap));
+        self
+    }
+
     /// Sets the character used for masking the password input.
     pub fn mask(mut self, mask: char) -> Self {
         self = Password(self.0.mask(mask));
@@ -64,10 +78,31 @@ impl Password {
         self
     }
 
+    /// Parses markup tags in the error messages of the validator.
+    pub fn error_message_markup(mut self) -> Self {
+        self = Password(self.0.error_message_markup());
+        self
+    }
+
+    /// Makes the prompt transient with the default summary of the masked password, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self = Password(self.0.transient());
+        self
+    }
+
+    /// Makes the prompt transient with the given format, which is given the masked password.
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self = Password(self.0.summary(summary));
+        self
+    }
+
     /// Displays the password prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the user's input.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    ///
+    /// The help of the key bindings is toggled with <kbd>F1</kbd>,
+    /// since `?` is typed into the password.
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
         self.0.prompt()
     }
 }
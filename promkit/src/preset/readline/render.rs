@@ -1,9 +1,20 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
+
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, snapshot::Snapshot, suggest::Suggest,
-    switch::ActiveKeySwitcher, text, text_editor, validate::ValidatorManager, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event,
+    fallback,
+    grapheme::StyledGraphemes,
+    help, listbox,
+    pane::Pane,
+    preset::{self, Summary},
+    snapshot::Snapshot,
+    suggest::Suggest,
+    switch::ActiveKeySwitcher,
+    text, text_editor,
+    validate::ValidatorManager,
+    Error, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -13,6 +24,8 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key bindings and their associated actions within the readline interface.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
     /// Holds a title's renderer state, used for rendering the title section.
     pub title_state: text::State,
     /// Holds a snapshot of the text editor's renderer state, used for rendering the text input area.
@@ -23,8 +36,12 @@ pub struct Renderer {
     pub suggest_snapshot: Snapshot<listbox::State>,
     /// Optional validator manager for input validation.
     pub validator: Option<ValidatorManager<str>>,
+    /// Whether the error messages contain markup tags.
+    pub error_message_markup: bool,
     /// Holds a snapshot of the error message's renderer state, used for rendering error messages.
     pub error_message_snapshot: Snapshot<text::State>,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -50,10 +67,14 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.error_message_snapshot.create_pane(width, height),
             self.text_editor_snapshot.create_pane(width, height),
             self.suggest_snapshot.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -61,4 +82,33 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        let summary = self.summary?;
+        let answer = match self.text_editor_snapshot.init().mask {
+            Some(mask) => ret.graphemes(true).map(|_| mask).collect(),
+            None => ret.clone(),
+        };
+        let mut title = preset::title_text(&self.title_state);
+        if title.is_empty() {
+            title = self.text_editor_snapshot.init().prefix.trim().to_string();
+        }
+        Some(summary(&title, &answer))
+    }
+
+    /// Reads a line and validates it with the validator, if any.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let line = fallback::read_line(input)?;
+        if let Some(validator) = &self.validator {
+            if !validator.validate(&line) {
+                let mut message = validator.generate_error_message(&line);
+                // The error message is returned without its markup tags.
+                if self.error_message_markup {
+                    message = StyledGraphemes::from_markup(message).to_string();
+                }
+                return Err(Error::Validation(message).into());
+            }
+        }
+        Ok(line)
+    }
 }
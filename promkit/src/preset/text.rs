@@ -2,39 +2,101 @@ use std::cell::RefCell;
 
 use crossterm::style::ContentStyle;
 
-use crate::{switch::ActiveKeySwitcher, text, Prompt};
+use crate::{
+    grapheme::Wrap,
+    help,
+    preset::{self, Summary},
+    switch::ActiveKeySwitcher,
+    text, Prompt,
+};
 
 pub mod keymap;
 pub mod render;
 
 pub struct Text {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     text_state: text::State,
+    summary: Option<Summary>,
 }
 
 impl Text {
     pub fn new<T: AsRef<str>>(text: T) -> Self {
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
             text_state: text::State {
                 text: text::Text::from(text),
                 style: Default::default(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
+            summary: None,
         }
     }
 
+    /// Creates a new `Text` from a string with ANSI escape sequences,
+    /// e.g. the colored output of another command, displayed with its colors.
+    pub fn from_ansi<T: AsRef<str>>(text: T) -> Self {
+        let mut ret = Self::new("");
+        ret.text_state.text = text::Text::from_ansi(text);
+        ret.text_state.keep_styles = true;
+        ret
+    }
+
+    /// Creates a new `Text` from a string with markup tags
+    /// (see `StyledGraphemes::from_markup`).
+    pub fn from_markup<T: AsRef<str>>(text: T) -> Self {
+        let mut ret = Self::new("");
+        ret.text_state.text = text::Text::from_markup(text);
+        ret.text_state.keep_styles = true;
+        ret
+    }
+
     pub fn style(mut self, style: ContentStyle) -> Self {
         self.text_state.style = style;
         self
     }
 
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                text_state: self.text_state,
-            },
-        })
+    /// Sets how lines longer than the width are wrapped,
+    /// e.g. at word boundaries with a hanging indent. They are broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.text_state.wrap = wrap;
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>?</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
+    /// Makes the prompt transient with the default summary of the first line, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format, which is given the first line
+    /// as the title and an empty answer.
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Creates a prompt that shows the text until it is closed.
+    ///
+    /// Without a terminal, the prompt returns immediately without reading stdin.
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            text_state: self.text_state,
+            summary: self.summary,
+        }))
     }
 }
@@ -1,14 +1,18 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
 
 use crate::{
-    crossterm::event::Event, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory, PromptSignal,
+    crossterm::event::Event, grapheme::StyledGraphemes, help, pane::Pane, preset::Summary,
+    switch::ActiveKeySwitcher, text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
 
 pub struct Renderer {
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
     pub text_state: text::State,
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -21,11 +25,31 @@ impl crate::Finalizer for Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![self.text_state.create_pane(width, height)]
+        vec![
+            self.text_state.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
+        ]
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn summary(&self, _: &Self::Return) -> Option<StyledGraphemes> {
+        let title = self
+            .text_state
+            .text
+            .items()
+            .first()
+            .map(|line| line.to_string().replace('\0', ""))
+            .unwrap_or_default();
+        self.summary.map(|summary| summary(&title, ""))
+    }
+
+    /// Does nothing, since there is no answer to read.
+    fn fallback(&mut self, _: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        Ok(())
+    }
 }
@@ -2,6 +2,10 @@ use std::cell::RefCell;
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::Wrap,
+    help,
+    indicator::Indicator,
+    preset::{self, Summary},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text::{self, Text},
@@ -16,10 +20,14 @@ pub mod render;
 /// and managing a hierarchical list of options.
 pub struct Tree {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// State for the help of the key bindings.
+    help_state: help::State,
     /// State for the title displayed above the tree.
     title_state: text::State,
     /// State for the tree itself.
     tree_state: tree::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    summary: Option<Summary>,
 }
 
 impl Tree {
@@ -30,13 +38,17 @@ impl Tree {
     /// * `root` - The root node of the tree.
     pub fn new(root: Node) -> Self {
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .describe("default", self::keymap::DEFAULT_BINDINGS),
+            help_state: help::State::new("?"),
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
                 lines: None,
+                wrap: Wrap::Char,
+                keep_styles: false,
             },
             tree_state: tree::State {
                 tree: tree::Tree::new(root),
@@ -45,8 +57,10 @@ impl Tree {
                 active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
                 inactive_item_style: StyleBuilder::new().build(),
                 lines: Default::default(),
+                indicator: Default::default(),
                 indent: 2,
             },
+            summary: None,
         }
     }
 
@@ -56,12 +70,27 @@ impl Tree {
         self
     }
 
+    /// Sets the title text with markup tags (see `StyledGraphemes::from_markup`),
+    /// e.g. `"[bold]Deploy[/] to [fg=red]prod[/]"`.
+    pub fn title_markup<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = Text::from_markup(text);
+        self.title_state.keep_styles = true;
+        self
+    }
+
     /// Sets the style for the title text.
     pub fn title_style(mut self, style: ContentStyle) -> Self {
         self.title_state.style = style;
         self
     }
 
+    /// Sets how a title longer than the width is wrapped, e.g. at word boundaries
+    /// with a hanging indent. It is broken at any character by default.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.title_state.wrap = wrap;
+        self
+    }
+
     /// Sets the symbol used to indicate a folded (collapsed) node.
     pub fn folded_symbol<T: AsRef<str>>(mut self, symbol: T) -> Self {
         self.tree_state.folded_symbol = symbol.as_ref().to_string();
@@ -92,6 +121,12 @@ impl Tree {
         self
     }
 
+    /// Sets the scrollbar and position footer shown with the tree.
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.tree_state.indicator = indicator;
+        self
+    }
+
     /// Sets the indentation level for rendering the tree data.
     pub fn indent(mut self, indent: usize) -> Self {
         self.tree_state.indent = indent;
@@ -103,16 +138,39 @@ impl Tree {
         self
     }
 
+    /// Makes the prompt transient with the default summary, see [`Summary`].
+    pub fn transient(mut self) -> Self {
+        self.summary = Some(preset::default_summary);
+        self
+    }
+
+    /// Makes the prompt transient with the given format of the summary, see [`Summary`].
+    pub fn summary(mut self, summary: Summary) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Shows a footer with the key bindings of the current mode.
+    /// The full help is toggled with <kbd>?</kbd> either way.
+    pub fn help_footer(mut self) -> Self {
+        self.help_state.footer = true;
+        self
+    }
+
     /// Displays the tree prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is a list of selected options.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                tree_state: self.tree_state,
-            },
-        })
+    ///
+    /// Without a terminal, the path of a node separated by `/`
+    /// is read from stdin instead, see [`crate::fallback`].
+    pub fn prompt(self) -> crate::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            help_state: self.help_state,
+            mouse_state: Default::default(),
+            summary: self.summary,
+            title_state: self.title_state,
+            tree_state: self.tree_state,
+        }))
     }
 }
@@ -3,6 +3,8 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
+    help::Binding,
+    mouse::Click,
     preset, PromptSignal,
 };
 
@@ -11,15 +13,26 @@ pub type Keymap = fn(
     renderer: &mut preset::tree::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Descriptions of the bindings of `default`, shown in the help.
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    Binding::new("↑↓", "move"),
+    Binding::new("space", "fold/unfold"),
+    Binding::new("enter", "select"),
+    Binding::new("ctrl+c", "cancel"),
+];
+
 /// Default key bindings for the tree.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
 /// | <kbd>Enter</kbd>       | Exit the tree view
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>?</kbd>           | Toggle the help
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
 /// | <kbd>Space</kbd>       | Toggle fold/unfold at the current node
+/// | Click                  | Move the selection to the clicked node
+/// | Double click           | Exit the tree view
 pub fn default(
     event: &Event,
     renderer: &mut preset::tree::render::Renderer,
@@ -36,7 +49,15 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(crate::Error::Interrupted.into()),
+
+        // Toggle the help.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('?'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.help_state.toggle(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -83,6 +104,17 @@ pub fn default(
             renderer.tree_state.tree.toggle();
         }
 
+        // Move the cursor to the clicked row, and confirm with a double click.
+        Event::Mouse(_) => match renderer.mouse_state.click(event) {
+            Some(Click::Single { row, width }) => {
+                if let Some(index) = renderer.tree_state.index_at(width, row) {
+                    renderer.tree_state.tree.move_to(index);
+                }
+            }
+            Some(Click::Double) => return Ok(PromptSignal::Quit),
+            None => (),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
@@ -1,8 +1,14 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io::BufRead};
 
 use crate::{
-    crossterm::event::Event, pane::Pane, switch::ActiveKeySwitcher, text, tree, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event,
+    fallback,
+    grapheme::StyledGraphemes,
+    help, mouse,
+    pane::{Pane, Region},
+    preset::{self, Summary},
+    switch::ActiveKeySwitcher,
+    text, tree, Error, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -12,10 +18,16 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key mappings specific to this renderer.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// State for the help of the key bindings.
+    pub help_state: help::State,
+    /// State for the clicks on the tree.
+    pub mouse_state: mouse::State,
     /// Snapshot of the title renderer.
     pub title_state: text::State,
     /// Snapshot of the tree renderer.
     pub tree_state: tree::State,
+    /// Formats the line that the prompt collapses into when it finishes, if any.
+    pub summary: Option<Summary>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -29,8 +41,12 @@ impl crate::Finalizer for Renderer {
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
         vec![
-            self.title_state.create_pane(width, height),
+            self.title_state
+                .create_pane(width, height)
+                .with_priority(preset::TITLE_PRIORITY),
             self.tree_state.create_pane(width, height),
+            self.help_state
+                .create_pane(self.keymap.borrow().bindings(), width, height),
         ]
     }
 
@@ -38,4 +54,28 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn on_draw(&mut self, regions: &[Option<Region>]) {
+        self.mouse_state.region = regions.get(1).copied().flatten();
+    }
+
+    fn summary(&self, ret: &Self::Return) -> Option<StyledGraphemes> {
+        self.summary
+            .map(|summary| summary(&preset::title_text(&self.title_state), &ret.join("/")))
+    }
+
+    /// Reads a line with the IDs of the nodes from the root
+    /// to the chosen node, separated by `/`.
+    fn fallback(&mut self, input: &mut dyn BufRead) -> anyhow::Result<Self::Return> {
+        let line = fallback::read_line(input)?;
+        let ids = line
+            .trim()
+            .split('/')
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>();
+        if !self.tree_state.tree.contains(&ids) {
+            return Err(Error::Validation(format!("no such node: {}", line.trim())).into());
+        }
+        Ok(ids)
+    }
 }
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 
+use crate::help::Binding;
+
 #[derive(Clone)]
 pub struct ActiveKeySwitcher<S> {
     mapping: HashMap<String, S>,
     active_key: String,
+    /// Descriptions of the bindings of each handler, shown in the help.
+    bindings: HashMap<String, &'static [Binding]>,
 }
 
 impl<S> ActiveKeySwitcher<S> {
@@ -12,6 +16,7 @@ impl<S> ActiveKeySwitcher<S> {
         Self {
             mapping: HashMap::new(),
             active_key: key.clone(),
+            bindings: HashMap::new(),
         }
         .register(key, handler)
     }
@@ -21,6 +26,12 @@ impl<S> ActiveKeySwitcher<S> {
         self
     }
 
+    /// Attaches descriptions of the bindings to the handler registered with the key.
+    pub fn describe<K: AsRef<str>>(mut self, key: K, bindings: &'static [Binding]) -> Self {
+        self.bindings.insert(key.as_ref().to_string(), bindings);
+        self
+    }
+
     pub fn switch<K: AsRef<str>>(&mut self, key: K) {
         let key = key.as_ref().to_string();
         if self.mapping.contains_key(&key) {
@@ -35,4 +46,13 @@ impl<S> ActiveKeySwitcher<S> {
     pub fn get(&self) -> &S {
         self.mapping.get(&self.active_key).unwrap()
     }
+
+    /// Returns the descriptions of the bindings of the active handler,
+    /// or an empty list if it has none.
+    pub fn bindings(&self) -> &'static [Binding] {
+        self.bindings
+            .get(&self.active_key)
+            .copied()
+            .unwrap_or_default()
+    }
 }
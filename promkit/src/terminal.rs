@@ -1,109 +1,1000 @@
-use std::io::{self, Write};
+use std::{
+    cmp::Reverse,
+    io::{self, IsTerminal, Write},
+    panic,
+    sync::{Arc, Mutex, Once, PoisonError, TryLockError},
+};
 
 use crate::{
-    crossterm::{cursor, style, terminal},
-    pane::Pane,
+    backend::{Backend, Crossterm},
+    crossterm::{cursor, event, style, terminal},
+    grapheme::StyledGraphemes,
+    pane::{Caret, CursorShape, Pane, Region},
 };
 
+/// Draws panes inline on a terminal through an arbitrary output writer.
+///
+/// The terminal keeps track of where the cursor is relative to the top of
+/// the prompt area instead of querying its absolute position, so that
+/// the prompt can be drawn to any target (e.g. stderr or `/dev/tty`)
+/// while stdout is redirected to a pipe.
+///
+/// For this reason, the former public `position` field no longer exists.
+/// The screen rows of the drawn panes are available from `Terminal::regions`.
 pub struct Terminal {
-    /// The current cursor position within the terminal.
-    pub position: (u16, u16),
+    /// The writer that the prompt is drawn to.
+    writer: SharedWriter,
+    /// The terminal that the size and events are read from.
+    backend: Arc<dyn Backend>,
+    /// The number of rows between the top of the prompt area and the cursor.
+    cursor_row: u16,
+    /// Whether a session is in progress, i.e. raw mode is enabled.
+    active: bool,
+    /// Whether the prompt is drawn on the alternate screen.
+    fullscreen: bool,
+    /// The rows drawn last, which are compared with the next frame
+    /// so that only changed rows are rewritten.
+    previous: Vec<StyledGraphemes>,
+    /// The caret drawn last, if any.
+    caret: Option<Caret>,
+    /// Whether the writer is known to be a terminal.
+    tty: bool,
+    /// The shape of the cursor currently shown, or `None` if it is hidden.
+    cursor_shape: Option<CursorShape>,
+    /// Whether mouse events are captured during a session.
+    mouse_capture: bool,
+    /// The screen row of the top of the prompt area, if known.
+    top: Option<u16>,
+    /// Where the panes drawn last are on screen, see `Terminal::regions`.
+    regions: Vec<Option<Region>>,
 }
 
 impl Terminal {
-    pub fn start_session(panes: &[Pane]) -> anyhow::Result<Self> {
-        let position = cursor::position()?;
-        let size = terminal::size()?;
-
-        // If the cursor is not at the beginning of a line (position.0 != 0),
-        // there are two scenarios to consider:
-        // 1. If the cursor is also at the last line of the terminal (size.1 == position.1 + 1),
-        //    the terminal is scrolled up by one line to make room.
-        // 2. Regardless of whether a scroll occurred, move the cursor to the beginning of the next line
-        //    to ensure the next output starts correctly.
-        if position.0 != 0 {
-            if size.1 == position.1 + 1 {
-                crossterm::queue!(io::stdout(), terminal::ScrollUp(1))?;
+    /// Creates a new `Terminal` that draws to the given writer.
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: SharedWriter(Arc::new(Mutex::new(Box::new(writer)))),
+            backend: Arc::new(Crossterm),
+            cursor_row: 0,
+            active: false,
+            fullscreen: false,
+            previous: vec![],
+            caret: None,
+            tty: true,
+            cursor_shape: None,
+            mouse_capture: false,
+            top: None,
+            regions: vec![],
+        }
+    }
+
+    /// Creates a new `Terminal` that draws to stdout.
+    pub fn stdout() -> Self {
+        Self {
+            tty: io::stdout().is_terminal(),
+            ..Self::new(io::stdout())
+        }
+    }
+
+    /// Returns whether the prompt can be drawn and operated interactively,
+    /// i.e. both the input of the backend and the writer are terminals.
+    ///
+    /// A writer given to `Terminal::new` is assumed to be a terminal
+    /// unless set otherwise with `Terminal::set_tty`.
+    pub fn is_interactive(&self) -> bool {
+        self.tty && self.backend.is_interactive()
+    }
+
+    /// Sets the terminal that the size and events are read from,
+    /// which is the terminal of the process (`Crossterm`) by default.
+    pub fn set_backend(&mut self, backend: Arc<dyn Backend>) {
+        self.backend = backend;
+    }
+
+    /// Returns the terminal that the size and events are read from.
+    pub fn backend(&self) -> &Arc<dyn Backend> {
+        &self.backend
+    }
+
+    /// Returns the size of the terminal as `(columns, rows)`.
+    pub fn size(&self) -> anyhow::Result<(u16, u16)> {
+        Ok(self.backend.size()?)
+    }
+
+    /// Sets whether the writer is a terminal.
+    pub fn set_tty(&mut self, tty: bool) {
+        self.tty = tty;
+    }
+
+    /// Sets whether the prompt is drawn on the alternate screen.
+    ///
+    /// In fullscreen mode, the prompt takes over the whole viewport,
+    /// and the original screen content is restored when the session ends.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.fullscreen = fullscreen;
+    }
+
+    /// Returns whether the prompt is drawn on the alternate screen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Sets whether mouse events (clicks and the wheel) are captured during a session.
+    ///
+    /// While the mouse is captured, the terminal cannot select text with it.
+    pub fn set_mouse_capture(&mut self, mouse_capture: bool) {
+        self.mouse_capture = mouse_capture;
+    }
+
+    /// Returns whether mouse events are captured during a session.
+    pub fn is_mouse_capture(&self) -> bool {
+        self.mouse_capture
+    }
+
+    /// Returns what a session of this terminal enables, for `restore`.
+    fn session(&self) -> Session {
+        Session {
+            writer: self.writer.clone(),
+            fullscreen: self.fullscreen,
+            mouse_capture: self.mouse_capture,
+        }
+    }
+
+    /// Returns where each of the panes drawn last is on screen,
+    /// in the order they were given to `Terminal::draw`.
+    ///
+    /// A region is `None` if the pane was not drawn (e.g. it was empty
+    /// or did not fit), or if the position of the prompt area on screen is unknown,
+    /// which is the case when stdout is not a terminal in inline mode.
+    pub fn regions(&self) -> &[Option<Region>] {
+        &self.regions
+    }
+
+    /// Enables raw mode and bracketed paste,
+    /// and hides the cursor to start drawing the prompt.
+    ///
+    /// This replaces the former `Terminal::start_session(&panes)` constructor:
+    /// create the terminal with `Terminal::stdout` or `Terminal::new`,
+    /// call this method, and then draw the panes with `Terminal::draw`.
+    pub fn start_session(&mut self) -> anyhow::Result<()> {
+        self.backend.enable_raw_mode()?;
+        self.active = true;
+        *SESSION.lock().unwrap_or_else(PoisonError::into_inner) = Some(self.session());
+        self.cursor_row = 0;
+        self.previous.clear();
+        self.caret = None;
+        self.cursor_shape = None;
+        self.top = None;
+        self.regions.clear();
+        if self.mouse_capture {
+            crossterm::queue!(self.writer, event::EnableMouseCapture)?;
+        }
+
+        if self.fullscreen {
+            self.top = Some(0);
+            crossterm::execute!(
+                self.writer,
+                terminal::EnterAlternateScreen,
+                cursor::MoveTo(0, 0),
+                terminal::Clear(terminal::ClearType::All),
+                cursor::Hide,
+                event::EnableBracketedPaste,
+            )?;
+            return Ok(());
+        }
+
+        // If the cursor is not at the beginning of a line,
+        // move it to the next line to ensure the prompt starts correctly.
+        if let Some((column, row)) = self.backend.cursor_position()? {
+            self.top = Some(row);
+            if column != 0 {
+                crossterm::queue!(self.writer, style::Print("\r\n"))?;
+                self.top = Some(row + 1);
             }
-            crossterm::queue!(io::stdout(), cursor::MoveToNextLine(1))?;
         }
 
-        // Calculate the total number of rows required by all panes.
-        let lines = panes
-            .iter()
-            .map(|pane| pane.visible_row_count())
-            .sum::<usize>();
-        // If the cursor is at the last line of the terminal,
-        // scroll up by the number of lines required by all panes,
-        // and then move the cursor up by the same number of lines
-        // to maintain its relative position.
-        if size.1 == position.1 + 1 {
+        crossterm::queue!(self.writer, cursor::Hide, event::EnableBracketedPaste)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Leaves the prompt area on screen, moves the cursor to the next line,
+    /// and restores the terminal to its original state.
+    /// In fullscreen mode, the alternate screen is left instead,
+    /// which brings back the original screen content.
+    pub fn end_session(&mut self) -> anyhow::Result<()> {
+        self.end(None)
+    }
+
+    /// Replaces the prompt area with a single summary line,
+    /// and restores the terminal to its original state.
+    /// In fullscreen mode, the summary is printed after leaving the alternate screen.
+    ///
+    /// A summary wider than the terminal is cut and ended with `…`,
+    /// since a wrapped summary would take more than one line.
+    pub fn end_session_with_summary(&mut self, summary: StyledGraphemes) -> anyhow::Result<()> {
+        let summary = match self.backend.size() {
+            Ok((width, _)) => summary.truncate(width as usize),
+            Err(_) => summary,
+        };
+        self.end(Some(summary))
+    }
+
+    fn end(&mut self, summary: Option<StyledGraphemes>) -> anyhow::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        SESSION
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take_if(|session| Arc::ptr_eq(&session.writer.0, &self.writer.0));
+        // The terminal may have been restored already, e.g. by the panic hook.
+        if !self.backend.is_raw_mode_enabled()? {
+            return Ok(());
+        }
+
+        if self.fullscreen {
+            crossterm::queue!(self.writer, terminal::LeaveAlternateScreen)?;
+            if let Some(summary) = summary {
+                crossterm::queue!(
+                    self.writer,
+                    style::Print(summary.styled_display()),
+                    style::Print("\r\n"),
+                )?;
+            }
+        } else {
+            match summary {
+                Some(summary) => self.draw_rows(vec![summary], None)?,
+                // The cursor may be placed at a caret above the last row.
+                None => self.move_to_row(self.previous.len().saturating_sub(1) as u16)?,
+            }
+            crossterm::queue!(self.writer, style::Print("\r\n"))?;
+        }
+        crossterm::execute!(
+            self.writer,
+            cursor::SetCursorStyle::DefaultUserShape,
+            cursor::Show,
+            event::DisableMouseCapture,
+            event::DisableBracketedPaste,
+        )?;
+        self.backend.disable_raw_mode()?;
+        Ok(())
+    }
+
+    /// Clears the whole screen including the scrollback,
+    /// and moves the prompt area to the top of the screen.
+    /// In fullscreen mode, the scrollback of the original screen is kept.
+    pub fn reset(&mut self) -> anyhow::Result<()> {
+        self.cursor_row = 0;
+        self.previous.clear();
+        self.top = Some(0);
+        let clear = if self.fullscreen {
+            terminal::ClearType::All
+        } else {
+            terminal::ClearType::Purge
+        };
+        crossterm::execute!(self.writer, cursor::MoveTo(0, 0), terminal::Clear(clear))
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Moves the cursor to the beginning of the given row of the prompt area.
+    ///
+    /// Rows below the last drawn row do not exist on screen yet,
+    /// so they are created with line feeds, which scroll the terminal up
+    /// when the cursor is on the last line.
+    fn move_to_row(&mut self, row: u16) -> anyhow::Result<()> {
+        if row < self.cursor_row {
             crossterm::queue!(
-                io::stdout(),
-                terminal::ScrollUp(lines as u16),
-                cursor::MoveToPreviousLine(lines as u16),
+                self.writer,
+                cursor::MoveToPreviousLine(self.cursor_row - row)
             )?;
+        } else if row > self.cursor_row {
+            let drawn = (self.previous.len() as u16).saturating_sub(1).min(row);
+            if drawn > self.cursor_row {
+                crossterm::queue!(self.writer, cursor::MoveToNextLine(drawn - self.cursor_row))?;
+            }
+            for _ in drawn.max(self.cursor_row)..row {
+                crossterm::queue!(self.writer, style::Print("\r\n"))?;
+            }
+        } else {
+            crossterm::queue!(self.writer, cursor::MoveToColumn(0))?;
         }
+        self.cursor_row = row;
+        Ok(())
+    }
 
-        io::stdout().flush()?;
+    /// Draws the panes, rewriting only the rows that differ from the previous frame,
+    /// and places the cursor at the caret of the first pane that reports one.
+    ///
+    /// Where each pane is drawn can be looked up with `Terminal::regions` afterwards.
+    pub fn draw(&mut self, panes: &[Pane]) -> anyhow::Result<()> {
+        let (width, height) = self.size()?;
+        let (rows, caret) = Self::layout_with_caret(panes, height)
+            .unwrap_or_else(|| (vec![StyledGraphemes::from("⚠️ Insufficient Space")], None));
+        let drawn = rows.len() as u16;
+        self.draw_rows(rows, caret)?;
 
-        Ok(Self {
-            position: cursor::position()?,
-        })
+        // Rows drawn below the bottom of the screen scroll the prompt area up.
+        self.top = self.top.map(|top| top.min(height.saturating_sub(drawn)));
+        self.regions = match self.top {
+            Some(top) => Self::regions_within(panes, width, height)
+                .into_iter()
+                .map(|region| {
+                    region.map(|region| Region {
+                        top: top + region.top,
+                        ..region
+                    })
+                })
+                .collect(),
+            None => vec![None; panes.len()],
+        };
+        Ok(())
     }
 
-    pub fn draw(&mut self, panes: &[Pane]) -> anyhow::Result<()> {
-        let height = terminal::size()?.1;
+    /// Draws the rows, rewriting only the rows that differ from the previous frame.
+    ///
+    /// The cursor is left at the caret if given, and on the last row
+    /// of the prompt area otherwise. It is shown with the shape of the caret,
+    /// and hidden while the rows are drawn.
+    fn draw_rows(
+        &mut self,
+        rows: Vec<StyledGraphemes>,
+        caret: Option<Caret>,
+    ) -> anyhow::Result<()> {
+        if self.cursor_shape.take().is_some() {
+            crossterm::queue!(self.writer, cursor::Hide)?;
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            if self.previous.get(i) == Some(row) {
+                continue;
+            }
+            self.move_to_row(i as u16)?;
+            // The line is cleared before printing rather than after,
+            // since clearing right after a full-width row would erase its last cell.
+            crossterm::queue!(
+                self.writer,
+                terminal::Clear(terminal::ClearType::CurrentLine),
+                style::Print(row.styled_display()),
+            )?;
+        }
+
+        if rows.len() < self.previous.len() {
+            self.move_to_row(rows.len() as u16)?;
+            crossterm::queue!(
+                self.writer,
+                terminal::Clear(terminal::ClearType::FromCursorDown)
+            )?;
+        }
+        let last = rows.len().saturating_sub(1) as u16;
+        self.previous = rows;
+        self.caret = caret;
 
+        match caret {
+            Some(caret) => {
+                self.move_to_row((caret.row as u16).min(last))?;
+                crossterm::queue!(self.writer, cursor::MoveToColumn(caret.column as u16))?;
+                if let Some(shape) = caret.shape {
+                    crossterm::queue!(
+                        self.writer,
+                        cursor::SetCursorStyle::from(shape),
+                        cursor::Show
+                    )?;
+                    self.cursor_shape = Some(shape);
+                }
+            }
+            None => self.move_to_row(last)?,
+        }
+
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the rows drawn last and the caret placed on them, if any.
+    pub(crate) fn screen(&self) -> (&[StyledGraphemes], Option<Caret>) {
+        (&self.previous, self.caret)
+    }
+
+    /// Lays out the given panes vertically into the rows to be drawn
+    /// within a viewport of the specified height.
+    ///
+    /// Each pane first receives its minimum number of rows, and the remaining space
+    /// is given to the panes in order of priority, each up to its maximum,
+    /// so that panes with the same priority are filled greedily from the top.
+    /// If the minimums do not fit, the panes with the lowest priority
+    /// (the bottom-most among equals) are dropped until they do.
+    /// Empty panes are skipped.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the viewport has no rows for any non-empty pane.
+    pub fn layout(panes: &[Pane], height: u16) -> Option<Vec<StyledGraphemes>> {
+        Self::layout_with_caret(panes, height).map(|(rows, _)| rows)
+    }
+
+    /// Lays out the given panes in the same way as `Terminal::layout`,
+    /// and also returns the caret of the first pane that reports one within the viewport,
+    /// with its row relative to the top of the rows.
+    pub fn layout_with_caret(
+        panes: &[Pane],
+        height: u16,
+    ) -> Option<(Vec<StyledGraphemes>, Option<Caret>)> {
         let viewable_panes = panes
             .iter()
             .filter(|pane| !pane.is_empty())
             .collect::<Vec<&Pane>>();
 
-        if height < viewable_panes.len() as u16 {
-            return crossterm::execute!(
-                io::stdout(),
-                terminal::Clear(terminal::ClearType::FromCursorDown),
-                style::Print("⚠️ Insufficient Space"),
-            )
-            .map_err(anyhow::Error::from);
+        if height == 0 && !viewable_panes.is_empty() {
+            return None;
+        }
+
+        let mut rows = vec![];
+        let mut caret = None;
+        for (pane, viewport_height) in viewable_panes
+            .iter()
+            .zip(Self::allocate(&viewable_panes, height as usize))
+        {
+            if viewport_height == 0 {
+                continue;
+            }
+            if caret.is_none() {
+                caret = pane.extract_caret(viewport_height).map(|c| Caret {
+                    row: rows.len() + c.row,
+                    ..c
+                });
+            }
+            rows.extend(pane.extract(viewport_height));
+        }
+        Some((rows, caret))
+    }
+
+    /// Lays out the given panes in the same way as `Terminal::layout`,
+    /// and returns where each pane is drawn within a viewport of the specified size,
+    /// with the rows relative to the top of the viewport.
+    ///
+    /// The regions are in the same order as the panes,
+    /// and are `None` for the panes that are not drawn.
+    pub fn regions_within(panes: &[Pane], width: u16, height: u16) -> Vec<Option<Region>> {
+        let viewable_panes = panes
+            .iter()
+            .filter(|pane| !pane.is_empty())
+            .collect::<Vec<&Pane>>();
+        let mut sizes = Self::allocate(&viewable_panes, height as usize).into_iter();
+
+        let mut top = 0;
+        panes
+            .iter()
+            .map(|pane| {
+                if pane.is_empty() {
+                    return None;
+                }
+                let viewport_height = sizes.next().unwrap_or_default();
+                if viewport_height == 0 {
+                    return None;
+                }
+                let region = pane.extract_region(top, width, viewport_height);
+                top += pane.extract(viewport_height).len() as u16;
+                Some(region)
+            })
+            .collect()
+    }
+
+    /// Returns the number of rows given to each pane, where 0 means the pane is dropped.
+    fn allocate(panes: &[&Pane], height: usize) -> Vec<usize> {
+        let mut kept = (0..panes.len()).collect::<Vec<_>>();
+        loop {
+            let min_rows = kept.iter().map(|&i| panes[i].min_rows()).sum::<usize>();
+            if kept.len() <= 1 || min_rows <= height {
+                break;
+            }
+            let Some(lowest) = kept.iter().map(|&i| panes[i].priority()).min() else {
+                break;
+            };
+            // Among the panes with the lowest priority, the one that frees just enough rows
+            // for the others is dropped, so that no more panes are dropped than needed.
+            // Otherwise the bottom-most one is dropped and the others are checked again.
+            let candidates = kept
+                .iter()
+                .enumerate()
+                .filter(|(_, &i)| panes[i].priority() == lowest);
+            let excess = min_rows - height;
+            let pos = candidates
+                .clone()
+                .filter(|(_, &i)| panes[i].min_rows() >= excess)
+                .min_by_key(|(_, &i)| (panes[i].min_rows(), Reverse(i)))
+                .or_else(|| candidates.max_by_key(|(_, &i)| i))
+                .map(|(pos, _)| pos);
+            if let Some(pos) = pos {
+                kept.remove(pos);
+            }
+        }
+
+        let mut sizes = vec![0; panes.len()];
+        let mut remaining = height;
+        for &i in &kept {
+            sizes[i] = panes[i].min_rows().min(remaining);
+            remaining -= sizes[i];
+        }
+
+        kept.sort_by_key(|&i| (Reverse(panes[i].priority()), i));
+        for i in kept {
+            let extra = panes[i]
+                .desired_rows()
+                .saturating_sub(sizes[i])
+                .min(remaining);
+            sizes[i] += extra;
+            remaining -= extra;
+        }
+        sizes
+    }
+}
+
+/// A writer that is shared between a `Terminal` and its running session,
+/// so that `restore` writes to the same target as the prompt.
+#[derive(Clone)]
+struct SharedWriter(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .flush()
+    }
+}
+
+/// The session in progress, recorded by `Terminal::start_session` for `restore`.
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// The writer of a session and the modes it enabled.
+struct Session {
+    writer: SharedWriter,
+    fullscreen: bool,
+    mouse_capture: bool,
+}
+
+impl Session {
+    /// Writes the escape sequences that undo what the session enabled.
+    ///
+    /// If the writer is in use, e.g. when the panic happened while drawing,
+    /// the sequences are written to stderr instead.
+    fn restore(&self) -> io::Result<()> {
+        match self.writer.0.try_lock() {
+            Ok(mut writer) => self.write_to(&mut *writer),
+            Err(TryLockError::Poisoned(writer)) => self.write_to(&mut *writer.into_inner()),
+            Err(TryLockError::WouldBlock) => self.write_to(&mut io::stderr()),
         }
+    }
 
+    fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        if self.mouse_capture {
+            crossterm::queue!(writer, event::DisableMouseCapture)?;
+        }
+        crossterm::queue!(writer, event::DisableBracketedPaste)?;
+        if self.fullscreen {
+            crossterm::queue!(writer, terminal::LeaveAlternateScreen)?;
+        }
         crossterm::queue!(
-            io::stdout(),
-            cursor::MoveTo(self.position.0, self.position.1),
-            terminal::Clear(terminal::ClearType::FromCursorDown),
+            writer,
+            cursor::SetCursorStyle::DefaultUserShape,
+            cursor::Show,
         )?;
+        if !self.fullscreen {
+            crossterm::queue!(writer, style::Print("\r\n"))?;
+        }
+        writer.flush()
+    }
+}
+
+/// Restores the terminal to its original state if raw mode is enabled:
+/// disables raw mode, and undoes what the running session of a `Terminal` enabled,
+/// i.e. shows the cursor with its default shape, disables bracketed paste
+/// and mouse capture, and leaves the alternate screen in fullscreen mode.
+///
+/// Unlike `Terminal::end_session`, this does not need access to the `Terminal`,
+/// so it can be called from a panic hook or another thread.
+/// The escape sequences are written to the writer of the `Terminal`,
+/// and nothing is written if no session is running.
+pub fn restore() -> anyhow::Result<()> {
+    let session = SESSION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if !terminal::is_raw_mode_enabled()? {
+        return Ok(());
+    }
+    terminal::disable_raw_mode()?;
+
+    if let Some(session) = session {
+        session.restore()?;
+    }
+    Ok(())
+}
+
+/// Installs a panic hook that restores the terminal before the panic message is printed.
+///
+/// The previously installed hook (e.g. the default one printing the message)
+/// is called after the terminal is restored, so that the message is readable.
+/// This also works with `panic = "abort"`, where `Drop` implementations
+/// such as that of `Prompt` are not run. Installing the hook more than once has no effect.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore().ok();
+            hook(info);
+        }));
+    });
+}
+
+/// A guard that restores the terminal when it is dropped, see [`restore`].
+///
+/// This covers early returns and unwinding in code
+/// that uses the terminal outside of `Prompt`.
+///
+/// ```ignore
+/// let _guard = promkit::terminal::RestoreGuard::new();
+/// ```
+#[derive(Default)]
+pub struct RestoreGuard(());
+
+impl RestoreGuard {
+    /// Creates a new `RestoreGuard`.
+    pub fn new() -> Self {
+        Self(())
+    }
+}
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        restore().ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod restore {
+        use super::{super::*, draw_rows::Buffer};
+
+        #[test]
+        fn test() {
+            let buffer = Buffer::default();
+            let term = Terminal::new(buffer.clone());
+            term.session().restore().unwrap();
+
+            let mut expect = vec![];
+            crossterm::queue!(
+                expect,
+                event::DisableBracketedPaste,
+                cursor::SetCursorStyle::DefaultUserShape,
+                cursor::Show,
+                style::Print("\r\n"),
+            )
+            .unwrap();
+            assert_eq!(String::from_utf8(expect).unwrap(), buffer.take());
+        }
+
+        #[test]
+        fn test_with_fullscreen() {
+            let buffer = Buffer::default();
+            let mut term = Terminal::new(buffer.clone());
+            term.set_fullscreen(true);
+            term.set_mouse_capture(true);
+            term.session().restore().unwrap();
+
+            let mut expect = vec![];
+            crossterm::queue!(
+                expect,
+                event::DisableMouseCapture,
+                event::DisableBracketedPaste,
+                terminal::LeaveAlternateScreen,
+                cursor::SetCursorStyle::DefaultUserShape,
+                cursor::Show,
+            )
+            .unwrap();
+            assert_eq!(String::from_utf8(expect).unwrap(), buffer.take());
+        }
+    }
+
+    mod draw_rows {
+        use std::sync::{Arc, Mutex};
+
+        use super::super::*;
 
-        let mut used = 0;
+        #[derive(Clone, Default)]
+        pub(super) struct Buffer(Arc<Mutex<Vec<u8>>>);
 
-        let mut current_cursor_y = height.saturating_sub(self.position.1);
-        for (i, pane) in viewable_panes.iter().enumerate() {
-            let rows = pane.extract(
-                1.max(
-                    (height as usize)
-                        // -1 in this context signifies the exclusion of the current pane.
-                        .saturating_sub(used + viewable_panes.len() - 1 - i),
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        impl Buffer {
+            pub(super) fn take(&self) -> String {
+                String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+            }
+        }
+
+        fn rows(rows: &[&str]) -> Vec<StyledGraphemes> {
+            rows.iter().map(|row| StyledGraphemes::from(*row)).collect()
+        }
+
+        #[test]
+        fn test() {
+            let buffer = Buffer::default();
+            let mut term = Terminal::new(buffer.clone());
+            term.draw_rows(rows(&["apple", "banana", "cherry"]), None)
+                .unwrap();
+            let out = buffer.take();
+            assert!(out.contains("apple") && out.contains("banana") && out.contains("cherry"));
+
+            term.draw_rows(rows(&["apple", "grape", "cherry"]), None)
+                .unwrap();
+            let out = buffer.take();
+            assert!(out.contains("grape"));
+            assert!(!out.contains("apple") && !out.contains("cherry"));
+            assert_eq!(2, term.cursor_row);
+        }
+
+        #[test]
+        fn test_with_fewer_rows() {
+            let buffer = Buffer::default();
+            let mut term = Terminal::new(buffer.clone());
+            term.draw_rows(rows(&["apple", "banana", "cherry"]), None)
+                .unwrap();
+            buffer.take();
+
+            term.draw_rows(rows(&["apple"]), None).unwrap();
+            assert_eq!(
+                format!(
+                    "{}{}{}",
+                    cursor::MoveToPreviousLine(1),
+                    terminal::Clear(terminal::ClearType::FromCursorDown),
+                    cursor::MoveToPreviousLine(1),
                 ),
+                buffer.take(),
             );
-            used += rows.len();
-            for (j, row) in rows.iter().enumerate() {
-                crossterm::queue!(io::stdout(), style::Print(row.styled_display()))?;
-
-                current_cursor_y = current_cursor_y.saturating_sub(1);
-
-                if ((i == viewable_panes.len() - 1 && j != rows.len() - 1)
-                    || i != viewable_panes.len() - 1)
-                    && current_cursor_y == 0
-                {
-                    crossterm::queue!(io::stdout(), terminal::ScrollUp(1))?;
-                    self.position.1 = self.position.1.saturating_sub(1);
-                }
+            assert_eq!(0, term.cursor_row);
+        }
 
-                crossterm::queue!(io::stdout(), cursor::MoveToNextLine(1))?;
-            }
+        #[test]
+        fn test_without_changes() {
+            let buffer = Buffer::default();
+            let mut term = Terminal::new(buffer.clone());
+            term.draw_rows(rows(&["apple", "banana"]), None).unwrap();
+            buffer.take();
+
+            term.draw_rows(rows(&["apple", "banana"]), None).unwrap();
+            assert_eq!(format!("{}", cursor::MoveToColumn(0)), buffer.take());
+        }
+
+        #[test]
+        fn test_with_caret() {
+            let buffer = Buffer::default();
+            let mut term = Terminal::new(buffer.clone());
+            let caret = Caret {
+                row: 0,
+                column: 3,
+                shape: Some(CursorShape::Bar),
+            };
+            term.draw_rows(rows(&["apple", "banana"]), Some(caret))
+                .unwrap();
+            let out = buffer.take();
+            assert!(out.ends_with(&format!(
+                "{}{}{}{}",
+                cursor::MoveToPreviousLine(1),
+                cursor::MoveToColumn(3),
+                cursor::SetCursorStyle::SteadyBar,
+                cursor::Show,
+            )));
+            assert_eq!(0, term.cursor_row);
+
+            term.draw_rows(rows(&["apple", "banana"]), None).unwrap();
+            assert_eq!(
+                format!("{}{}", cursor::Hide, cursor::MoveToNextLine(1)),
+                buffer.take(),
+            );
+            assert_eq!(1, term.cursor_row);
+        }
+    }
+
+    mod regions_within {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let panes = vec![
+                Pane::new(vec![StyledGraphemes::from("title")], 0),
+                Pane::new(vec![], 0),
+                Pane::new(
+                    vec![
+                        StyledGraphemes::from("a"),
+                        StyledGraphemes::from("b"),
+                        StyledGraphemes::from("c"),
+                    ],
+                    0,
+                )
+                .with_footer(StyledGraphemes::from("1/3")),
+            ];
+            assert_eq!(
+                vec![
+                    Some(Region {
+                        top: 0,
+                        width: 10,
+                        start: 0,
+                        height: 1,
+                    }),
+                    None,
+                    Some(Region {
+                        top: 1,
+                        width: 10,
+                        start: 0,
+                        height: 2,
+                    }),
+                ],
+                Terminal::regions_within(&panes, 10, 4),
+            );
+        }
+    }
+
+    mod layout {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let panes = vec![
+                Pane::new(vec![StyledGraphemes::from("title")], 0),
+                Pane::new(vec![], 0),
+                Pane::new(
+                    vec![
+                        StyledGraphemes::from("a"),
+                        StyledGraphemes::from("b"),
+                        StyledGraphemes::from("c"),
+                    ],
+                    0,
+                ),
+            ];
+            assert_eq!(
+                Some(vec![
+                    StyledGraphemes::from("title"),
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from("b"),
+                ]),
+                Terminal::layout(&panes, 3),
+            );
+        }
+
+        #[test]
+        fn test_with_insufficient_space() {
+            let panes = vec![
+                Pane::new(vec![StyledGraphemes::from("a")], 0),
+                Pane::new(vec![StyledGraphemes::from("b")], 0),
+            ];
+            assert_eq!(
+                Some(vec![StyledGraphemes::from("a")]),
+                Terminal::layout(&panes, 1)
+            );
+            assert_eq!(None, Terminal::layout(&panes, 0));
+        }
+
+        #[test]
+        fn test_with_priority() {
+            let panes = vec![
+                Pane::new(
+                    vec![
+                        StyledGraphemes::from("title"),
+                        StyledGraphemes::from("more"),
+                    ],
+                    0,
+                )
+                .with_priority(-1),
+                Pane::new(
+                    vec![
+                        StyledGraphemes::from("a"),
+                        StyledGraphemes::from("b"),
+                        StyledGraphemes::from("c"),
+                    ],
+                    0,
+                ),
+                Pane::new(vec![StyledGraphemes::from("help")], 0).with_priority(-2),
+            ];
+            assert_eq!(
+                Some(vec![
+                    StyledGraphemes::from("title"),
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from("b"),
+                    StyledGraphemes::from("c"),
+                    StyledGraphemes::from("help"),
+                ]),
+                Terminal::layout(&panes, 5),
+            );
+            assert_eq!(
+                Some(vec![
+                    StyledGraphemes::from("title"),
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from("help"),
+                ]),
+                Terminal::layout(&panes, 3),
+            );
+            assert_eq!(
+                Some(vec![
+                    StyledGraphemes::from("title"),
+                    StyledGraphemes::from("a")
+                ]),
+                Terminal::layout(&panes, 2),
+            );
+            assert_eq!(
+                Some(vec![StyledGraphemes::from("a")]),
+                Terminal::layout(&panes, 1),
+            );
+        }
+
+        #[test]
+        fn test_with_min_and_max_rows() {
+            let list = vec![
+                StyledGraphemes::from("a"),
+                StyledGraphemes::from("b"),
+                StyledGraphemes::from("c"),
+            ];
+            let panes = vec![
+                Pane::new(list.clone(), 0).with_max_rows(2),
+                Pane::new(list, 0).with_min_rows(3),
+            ];
+            assert_eq!(Some(5), Terminal::layout(&panes, 10).map(|rows| rows.len()));
+            // Both panes do not fit, but the second one does on its own.
+            assert_eq!(
+                Some(vec![
+                    String::from("a"),
+                    String::from("b"),
+                    String::from("c")
+                ]),
+                Terminal::layout(&panes, 3)
+                    .map(|rows| rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()),
+            );
+            assert_eq!(
+                Some(StyledGraphemes::from("a")),
+                Terminal::layout(&panes, 4).map(|rows| rows[0].clone()),
+            );
+        }
+
+        #[test]
+        fn test_with_caret() {
+            let caret = Caret {
+                row: 2,
+                column: 1,
+                shape: None,
+            };
+            let panes = vec![
+                Pane::new(vec![StyledGraphemes::from("title")], 0),
+                Pane::new(
+                    vec![
+                        StyledGraphemes::from("a"),
+                        StyledGraphemes::from("b"),
+                        StyledGraphemes::from("c"),
+                    ],
+                    1,
+                )
+                .with_caret(Some(caret)),
+            ];
+            assert_eq!(
+                Some(Caret { row: 2, ..caret }),
+                Terminal::layout_with_caret(&panes, 3).unwrap().1,
+            );
+            assert_eq!(None, Terminal::layout_with_caret(&panes, 2).unwrap().1);
         }
-        io::stdout().flush()?;
-        Ok(())
     }
 }
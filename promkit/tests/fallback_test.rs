@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod readline {
+    use promkit::{
+        preset::{confirm::Confirm, readline::Readline},
+        Error, Renderer,
+    };
+
+    #[test]
+    fn test() {
+        let mut p = Readline::default().prompt().unwrap();
+        let mut input = "hello\nworld\n".as_bytes();
+        assert_eq!("hello", p.renderer.fallback(&mut input).unwrap());
+        assert_eq!("world", p.renderer.fallback(&mut input).unwrap());
+        assert!(matches!(
+            p.renderer.fallback(&mut input).map_err(Error::from),
+            Err(Error::Eof)
+        ));
+    }
+
+    #[test]
+    fn test_with_invalid_input() {
+        let mut p = Readline::default()
+            .validator(|text| text.len() > 3, |text| format!("got {}", text.len()))
+            .prompt()
+            .unwrap();
+        match p
+            .renderer
+            .fallback(&mut "ab\n".as_bytes())
+            .map_err(Error::from)
+        {
+            Err(Error::Validation(message)) => assert_eq!("got 2", message),
+            _ => panic!("expected a validation error"),
+        }
+    }
+
+    #[test]
+    fn test_confirm() {
+        let mut p = Confirm::new("Do you?").prompt().unwrap();
+        assert_eq!("y", p.renderer.fallback(&mut "y\n".as_bytes()).unwrap());
+        assert!(p.renderer.fallback(&mut "maybe\n".as_bytes()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod listbox {
+    use promkit::{preset::listbox::Listbox, Renderer};
+
+    #[test]
+    fn test() {
+        let mut p = Listbox::new(["a", "b", "c"]).prompt().unwrap();
+        assert_eq!("c", p.renderer.fallback(&mut "c\n".as_bytes()).unwrap());
+        assert_eq!("b", p.renderer.fallback(&mut "1\n".as_bytes()).unwrap());
+        assert!(p.renderer.fallback(&mut "d\n".as_bytes()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod checkbox {
+    use promkit::{preset::checkbox::Checkbox, Renderer};
+
+    #[test]
+    fn test() {
+        let mut p = Checkbox::new(["a", "b", "c"]).prompt().unwrap();
+        assert_eq!(
+            vec!["a", "c"],
+            p.renderer.fallback(&mut "c, 0\n".as_bytes()).unwrap()
+        );
+        assert_eq!(
+            vec!["a", "b"],
+            p.renderer.fallback(&mut "b, a, 1\n".as_bytes()).unwrap()
+        );
+        assert!(p
+            .renderer
+            .fallback(&mut "\n".as_bytes())
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod answers {
+    use std::io;
+
+    use promkit::{
+        answers::Answers,
+        preset::{checkbox::Checkbox, listbox::Listbox, readline::Readline},
+        Error,
+    };
+
+    #[test]
+    fn test() {
+        let mut p = Listbox::new(["a", "b", "c"])
+            .prompt()
+            .unwrap()
+            .id("choice")
+            .answers(Answers::default().with("choice", "b"));
+        assert_eq!("b", p.run().unwrap());
+    }
+
+    #[test]
+    fn test_with_invalid_answer() {
+        let mut p = Listbox::new(["a", "b", "c"])
+            .prompt()
+            .unwrap()
+            .writer(io::sink())
+            .tty(false)
+            .id("choice")
+            .answers(Answers::default().with("choice", "d"));
+        assert!(matches!(p.run(), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_with_empty_answer() {
+        let mut p = Readline::default()
+            .prompt()
+            .unwrap()
+            .writer(io::sink())
+            .tty(false)
+            .id("name")
+            .answers(Answers::default().with("name", ""));
+        assert_eq!("", p.run().unwrap());
+
+        let mut p = Checkbox::new(["a", "b"])
+            .prompt()
+            .unwrap()
+            .writer(io::sink())
+            .tty(false)
+            .id("choices")
+            .answers(Answers::default().with("choices", ""));
+        assert!(p.run().unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod run_with_receiver {
+    use std::{io, sync::mpsc};
+
+    use promkit::{
+        crossterm::event::Event, pane::Pane, Finalizer, MessageHandler, Prompt, PromptSignal,
+        Renderer,
+    };
+
+    /// Counts the messages, and answers with the count without reading the input.
+    struct Counter(usize);
+
+    impl Finalizer for Counter {
+        type Return = usize;
+
+        fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+            Ok(self.0)
+        }
+    }
+
+    impl Renderer for Counter {
+        fn create_panes(&self, _: u16, _: u16) -> Vec<Pane> {
+            vec![]
+        }
+
+        fn evaluate(&mut self, _: &Event) -> anyhow::Result<PromptSignal> {
+            Ok(PromptSignal::Quit)
+        }
+
+        fn fallback(&mut self, _: &mut dyn io::BufRead) -> anyhow::Result<Self::Return> {
+            Ok(self.0)
+        }
+    }
+
+    impl MessageHandler<usize> for Counter {
+        fn on_message(&mut self, _: usize) -> anyhow::Result<PromptSignal> {
+            self.0 += 1;
+            Ok(PromptSignal::Continue)
+        }
+    }
+
+    #[test]
+    fn test_with_open_sender() {
+        let (sender, receiver) = mpsc::channel();
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+        // The sender is still open, as with a producer that never stops.
+        let ret = Prompt::new(Counter(0))
+            .writer(io::sink())
+            .tty(false)
+            .run_with_receiver(receiver)
+            .unwrap();
+        assert_eq!(2, ret);
+        drop(sender);
+    }
+}
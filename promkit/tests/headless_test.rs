@@ -0,0 +1,609 @@
+#[cfg(test)]
+mod readline {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyModifiers},
+        grapheme::StyledGraphemes,
+        headless::Headless,
+        pane::{Caret, CursorShape},
+        preset::readline::Readline,
+        suggest::Suggest,
+        Error,
+    };
+
+    #[test]
+    fn test() {
+        let mut p = Readline::default().title("Hi!").prompt().unwrap();
+        let output = Headless::new(20, 5)
+            .text("hello")
+            .key(KeyCode::Backspace, KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("hell")), output.ret);
+        assert_eq!(
+            vec!["Hi!", "❯❯ hell "],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_caret() {
+        let mut p = Readline::default()
+            .title("Hi!")
+            .cursor_shape(Some(CursorShape::Bar))
+            .prompt()
+            .unwrap();
+        let output = Headless::new(6, 5)
+            .text("hello")
+            .key(KeyCode::Left, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            Some(Caret {
+                row: 2,
+                column: 1,
+                shape: Some(CursorShape::Bar),
+            }),
+            output.caret,
+        );
+    }
+
+    #[test]
+    fn test_with_help_footer() {
+        let mut p = Readline::default()
+            .enable_suggest(Suggest::from_iter(["apple", "apricot"]))
+            .help_footer()
+            .prompt()
+            .unwrap();
+        let output = Headless::new(40, 10)
+            .text("ap")
+            .key(KeyCode::Tab, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            Some("tab/↓ next suggestion  F1 help"),
+            output.screen.last().map(|row| row.to_string()).as_deref(),
+        );
+
+        let output = Headless::new(40, 20)
+            .key(KeyCode::Esc, KeyModifiers::NONE)
+            .key(KeyCode::F(1), KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            Some("F1           close help"),
+            output.screen.last().map(|row| row.to_string()).as_deref(),
+        );
+    }
+
+    #[test]
+    fn test_with_paste() {
+        let mut p = Readline::default().prompt().unwrap();
+        let output = Headless::new(20, 5)
+            .text("a")
+            .event(Event::Paste(String::from("b\nc")))
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("ab c")), output.ret);
+    }
+
+    #[test]
+    fn test_with_paste_on_suggest() {
+        let mut p = Readline::default()
+            .enable_suggest(Suggest::from_iter(["apple", "apricot"]))
+            .prompt()
+            .unwrap();
+        let output = Headless::new(20, 5)
+            .text("ap")
+            .key(KeyCode::Tab, KeyModifiers::NONE)
+            .event(Event::Paste(String::from(" pie")))
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("apple pie")), output.ret);
+    }
+
+    #[test]
+    fn test_with_invalid_input() {
+        let mut p = Readline::default()
+            .validator(|text| text.len() > 3, |text| format!("got {}", text.len()))
+            .prompt()
+            .unwrap();
+        let output = Headless::new(20, 5)
+            .text("ab")
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(None, output.ret);
+        assert_eq!(
+            vec!["got 2", "❯❯ ab "],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_markup() {
+        let mut p = Readline::default()
+            .title_markup("[bold]Deploy[/] to [fg=red]prod[/]")
+            .validator(
+                |text| text == "yes",
+                |text| format!("[fg=red]{}[/] is not [bold]yes[/]", text),
+            )
+            .error_message_markup()
+            .prompt()
+            .unwrap();
+        let output = Headless::new(30, 5)
+            .text("no")
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            vec!["Deploy to prod", "no is not yes", "❯❯ no "],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+        assert!(output.screen[0]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_markup("[fg=red]prod[/]").to_ansi()));
+    }
+
+    #[test]
+    fn test_without_markup() {
+        let mut p = Readline::default()
+            .title("[bold]Step[/] [1/2]")
+            .validator(
+                |text| text == "yes",
+                |text| format!("[{}] is not yes", text),
+            )
+            .prompt()
+            .unwrap();
+        let output = Headless::new(30, 5)
+            .text("[fg=red]no[/]")
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            vec![
+                "[bold]Step[/] [1/2]",
+                "[[fg=red]no[/]] is not yes",
+                "❯❯ [fg=red]no[/] ",
+            ],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_interruption() {
+        let mut p = Readline::default().prompt().unwrap();
+        let ret = Headless::new(20, 5)
+            .key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+            .run(&mut p);
+        assert!(matches!(ret, Err(Error::Interrupted)));
+    }
+
+    #[test]
+    fn test_with_eof() {
+        let mut p = Readline::default().prompt().unwrap();
+        let output = Headless::new(20, 5)
+            .text("a")
+            .key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(None, output.ret);
+
+        let ret = Headless::new(20, 5)
+            .key(KeyCode::Backspace, KeyModifiers::NONE)
+            .key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+            .run(&mut p);
+        assert!(matches!(ret, Err(Error::Eof)));
+    }
+}
+
+#[cfg(test)]
+mod listbox {
+    use promkit::{
+        crossterm::event::{KeyCode, KeyModifiers},
+        grapheme::{StyledGraphemes, Wrap},
+        headless::Headless,
+        indicator::{Indicator, Scrollbar},
+        preset::listbox::Listbox,
+        MessageHandler,
+    };
+
+    #[test]
+    fn test_with_wrap() {
+        let render = |p: Listbox| {
+            Headless::new(8, 4)
+                .render(&p.prompt().unwrap().renderer)
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>()
+        };
+        let title = || Listbox::new(["a"]).title("pick a branch");
+        assert_eq!(vec!["pick a b", "ranch", "❯ a"], render(title()));
+        assert_eq!(
+            vec!["pick a", "  branch", "❯ a"],
+            render(title().wrap(Wrap::Word { indent: 2 })),
+        );
+    }
+
+    #[test]
+    fn test_with_summary() {
+        let mut p = Listbox::new(["a", "b"])
+            .title("pick")
+            .transient()
+            .prompt()
+            .unwrap();
+        let output = Headless::new(10, 5)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("b")), output.ret);
+        assert_eq!(
+            vec!["✔ pick · b"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_indicator() {
+        let p = Listbox::new(["a", "b", "c", "d", "e", "f"])
+            .title("pick")
+            .indicator(Indicator {
+                scrollbar: Some(Scrollbar::default()),
+                footer: Some(Default::default()),
+            })
+            .prompt()
+            .unwrap();
+        assert_eq!(
+            vec!["pick", "❯ a  █", "  b  │", "  c  │", "1/6"],
+            Headless::new(6, 5)
+                .render(&p.renderer)
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_indicator_at_last_item() {
+        let mut p = Listbox::new(["a", "b", "c", "d", "e", "f"])
+            .title("pick")
+            .indicator(Indicator {
+                scrollbar: Some(Scrollbar::default()),
+                footer: Some(Default::default()),
+            })
+            .prompt()
+            .unwrap();
+        let mut headless = Headless::new(6, 5);
+        for _ in 0..5 {
+            headless = headless.key(KeyCode::Down, KeyModifiers::NONE);
+        }
+        let output = headless.run(&mut p).unwrap();
+        assert_eq!(
+            vec!["pick", "❯ f  │", "     │", "     █", "6/6"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_small_terminal() {
+        let p = Listbox::new(["a", "b"]).title("pick").prompt().unwrap();
+        assert_eq!(
+            vec!["❯ a"],
+            Headless::new(10, 1)
+                .render(&p.renderer)
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_click() {
+        let mut p = Listbox::new(["a", "bbbbbbbbbbbb", "c"])
+            .title("pick")
+            .prompt()
+            .unwrap();
+        let output = Headless::new(6, 10)
+            .click(0, 0)
+            .click(0, 5)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(None, output.ret);
+        assert_eq!(
+            vec!["pick", "❯ c"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut p = Listbox::new(["a", "bbbbbbbbbbbb", "c"])
+            .title("pick")
+            .prompt()
+            .unwrap();
+        let output = Headless::new(6, 10)
+            .click(0, 3)
+            .click(0, 3)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("bbbbbbbbbbbb")), output.ret);
+    }
+
+    #[test]
+    fn test_with_message() {
+        let mut p = Listbox::new(["a"]).prompt().unwrap();
+        p.renderer.on_message("b").unwrap();
+        p.renderer.on_message("c").unwrap();
+        let output = Headless::new(10, 5)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("c")), output.ret);
+    }
+
+    #[test]
+    fn test_with_ansi_message() {
+        let mut p = Listbox::from_ansi(["a"]).prompt().unwrap();
+        p.renderer.on_message("\x1b[31mb\x1b[0m").unwrap();
+        let output = Headless::new(10, 5)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("b")), output.ret);
+        assert!(output.screen[0]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_ansi("\x1b[31mb").to_ansi()));
+    }
+}
+
+#[cfg(test)]
+mod query_selector {
+    use promkit::{
+        crossterm::event::{KeyCode, KeyModifiers},
+        grapheme::StyledGraphemes,
+        headless::Headless,
+        preset::query_selector::QuerySelector,
+        MessageHandler,
+    };
+
+    #[test]
+    fn test() {
+        let mut p = QuerySelector::new(["apple", "banana", "cherry", "grape"], |text, items| {
+            items
+                .iter()
+                .filter(|item| item.contains(text))
+                .cloned()
+                .collect()
+        })
+        .prompt()
+        .unwrap();
+        let output = Headless::new(20, 5)
+            .text("an")
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("banana")), output.ret);
+        assert_eq!(
+            vec!["❯❯ an ", "❯ banana"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_message() {
+        let mut p = QuerySelector::new(["apple"], |text, items| {
+            items
+                .iter()
+                .filter(|item| item.contains(text))
+                .cloned()
+                .collect()
+        })
+        .prompt()
+        .unwrap();
+        let output = Headless::new(20, 5).text("an").run(&mut p).unwrap();
+        assert_eq!(
+            vec!["❯❯ an "],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        p.renderer.on_message("banana").unwrap();
+        p.renderer.on_message("cherry").unwrap();
+        p.renderer.on_message("mango").unwrap();
+        let output = Headless::new(20, 5)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("mango")), output.ret);
+        assert_eq!(
+            vec!["❯❯ an ", "❯ mango"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_ansi() {
+        let mut p = QuerySelector::from_ansi(["\x1b[31mapple\x1b[0m", "banana"], |text, items| {
+            items
+                .iter()
+                .filter(|item| item.contains(text))
+                .cloned()
+                .collect()
+        })
+        .prompt()
+        .unwrap();
+        p.renderer.on_message("\x1b[1;32mapricot\x1b[0m").unwrap();
+        let output = Headless::new(20, 5)
+            .text("ap")
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(String::from("apple")), output.ret);
+        assert_eq!(
+            vec!["❯❯ ap ", "❯ apple", "  apricot"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+        // The items keep their colors through the filter.
+        assert!(output.screen[1]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_ansi("\x1b[31mapple").to_ansi()));
+        assert!(output.screen[2]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_ansi("\x1b[1;32mapricot").to_ansi()));
+    }
+
+    #[test]
+    fn test_with_ansi_and_same_text() {
+        let mut p = QuerySelector::from_ansi(
+            ["\x1b[31mapple\x1b[0m", "banana", "\x1b[32mapple\x1b[0m"],
+            |text, items| {
+                items
+                    .iter()
+                    .filter(|item| item.contains(text))
+                    .cloned()
+                    .collect()
+            },
+        )
+        .prompt()
+        .unwrap();
+        let output = Headless::new(20, 5).text("p").run(&mut p).unwrap();
+        // Each result keeps the color of its own item, not of the other one with the same text.
+        assert!(output.screen[1]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_ansi("\x1b[31mapple").to_ansi()));
+        assert!(output.screen[2]
+            .to_ansi()
+            .ends_with(&StyledGraphemes::from_ansi("\x1b[32mapple").to_ansi()));
+    }
+}
+
+#[cfg(test)]
+mod json {
+    use std::str::FromStr;
+
+    use promkit::{
+        crossterm::event::{KeyCode, KeyModifiers},
+        headless::Headless,
+        jsonstream::JsonStream,
+        preset::json::Json,
+        serde_json,
+    };
+
+    #[test]
+    fn test() {
+        let input = serde_json::Value::from_str(r#"{"a": 1, "b": [true, null]}"#).unwrap();
+        let mut p = Json::new(JsonStream::new([&input])).prompt().unwrap();
+        let output = Headless::new(20, 10)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Down, KeyModifiers::NONE)
+            .key(KeyCode::Char(' '), KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(Some(()), output.ret);
+        assert_eq!(
+            vec!["  \"b\": […]", "}"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_with_click() {
+        let input = serde_json::Value::from_str(r#"{"a": 1, "b": [true, null]}"#).unwrap();
+        let mut p = Json::new(JsonStream::new([&input])).prompt().unwrap();
+        let output = Headless::new(20, 10)
+            .click(0, 2)
+            .key(KeyCode::Char(' '), KeyModifiers::NONE)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            vec!["  \"b\": […]", "}"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod text {
+    use promkit::{
+        crossterm::event::{KeyCode, KeyModifiers},
+        headless::Headless,
+        preset::text::Text,
+    };
+
+    #[test]
+    fn test_with_summary() {
+        let mut p = Text::new("Release notes\n\n- a\n- b")
+            .transient()
+            .prompt()
+            .unwrap();
+        let output = Headless::new(20, 5)
+            .key(KeyCode::Enter, KeyModifiers::NONE)
+            .run(&mut p)
+            .unwrap();
+        assert_eq!(
+            vec!["✔ Release notes"],
+            output
+                .screen
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>(),
+        );
+    }
+}